@@ -0,0 +1,274 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+use serde::Deserialize;
+
+use crate::error::GallifreyanError;
+use crate::tree::{get_c, get_v, Letter, Vowel};
+use crate::tree::Letter::{COpt, VOpt};
+use crate::tree::Arc::{self, Above, Big, Small, On};
+use crate::tree::Marks::{Dot, Line};
+use crate::tree::Vowels::{self, A, E, I, O, U};
+
+//which spellings give which letters, Sherman's own unless a table is loaded from a file
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Alphabet {
+    singles: HashMap<char,Letter>,
+    doubles: HashMap<(char,char),Letter>,//digraphs and double vowels, tried before singles
+}
+
+impl Alphabet {
+    pub fn sherman() -> &'static Alphabet {
+        static SHERMAN:OnceLock<Alphabet> = OnceLock::new();
+        SHERMAN.get_or_init(|| Alphabet {
+            singles: HashMap::from([
+                ('a',get_v(A,false)),
+                ('e',get_v(E,false)),
+                ('i',get_v(I,false)),
+                ('o',get_v(O,false)),
+                ('u',get_v(U,false)),
+                ('b',get_c(Big,None)),
+                ('d',get_c(Big,Some(Dot(3)))),
+                ('g',get_c(Big,Some(Line(1)))),
+                ('h',get_c(Big,Some(Line(2)))),
+                ('f',get_c(Big,Some(Line(3)))),
+                ('j',get_c(Above,None)),
+                ('k',get_c(Above,Some(Dot(2)))),
+                ('l',get_c(Above,Some(Dot(3)))),
+                ('c',get_c(Above,Some(Dot(4)))),
+                ('n',get_c(Above,Some(Line(1)))),
+                ('p',get_c(Above,Some(Line(2)))),
+                ('m',get_c(Above,Some(Line(3)))),
+                ('t',get_c(Small,None)),
+                ('r',get_c(Small,Some(Dot(3)))),
+                ('v',get_c(Small,Some(Line(1)))),
+                ('w',get_c(Small,Some(Line(2)))),
+                ('s',get_c(Small,Some(Line(3)))),
+                ('y',get_c(On,Some(Dot(2)))),
+                ('z',get_c(On,Some(Dot(3)))),
+                ('q',get_c(On,Some(Dot(4)))),
+                ('x',get_c(On,Some(Line(2)))),
+            ]),
+            doubles: HashMap::from([
+                (('c','h'),get_c(Big,Some(Dot(2)))),
+                (('n','d'),get_c(Big,Some(Dot(4)))),
+                (('p','h'),get_c(Above,Some(Dot(1)))),
+                (('w','h'),get_c(Small,Some(Dot(1)))),
+                (('s','h'),get_c(Small,Some(Dot(2)))),
+                (('n','t'),get_c(Small,Some(Dot(4)))),
+                (('t','h'),get_c(On,None)),
+                (('g','h'),get_c(On,Some(Dot(1)))),
+                (('q','u'),get_c(On,Some(Line(1)))),
+                (('n','g'),get_c(On,Some(Line(3)))),
+                (('a','a'),get_v(A,true)),
+                (('e','e'),get_v(E,true)),
+                (('i','i'),get_v(I,true)),
+                (('o','o'),get_v(O,true)),
+                (('u','u'),get_v(U,true)),
+            ]),
+        })
+    }
+    pub fn single(&self, c:char) -> Option<&Letter> {
+        self.singles.get(&c)
+    }
+    pub fn double(&self, first:char, second:char) -> Option<&Letter> {
+        self.doubles.get(&(first,second))
+    }
+    pub fn spellings(&self) -> Vec<(String,&Letter)> {//every spelling in alphabetical order, singles before doubles
+        let mut singles:Vec<(String,&Letter)> = self.singles.iter().map(|(c,l)| (c.to_string(), l)).collect();
+        singles.sort_by(|a,b| a.0.cmp(&b.0));
+        let mut doubles:Vec<(String,&Letter)> = self.doubles.iter().map(|((a,b),l)| (format!("{a}{b}"), l)).collect();
+        doubles.sort_by(|a,b| a.0.cmp(&b.0));
+        singles.append(&mut doubles);
+        return singles;
+    }
+    pub fn spell(&self, letter:&Letter) -> Option<String> {//the first spelling that gives this letter, none if nothing does
+        if let COpt(consonant) = letter && let Some(v) = &consonant.diacritic {
+            let bare = COpt(crate::tree::Consonant{diacritic:None, ..consonant.clone()});
+            return Some(self.spell(&bare)?+&self.spell(&VOpt(v.clone()))?);
+        }
+        return self.spellings().into_iter().find(|(_,l)| *l == letter).map(|(s,_)| s);
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::sherman().clone()
+    }
+}
+
+//the file format, the same shape in toml and json:
+//  [letters]
+//  a = { vowel = "a" }
+//  ee = { vowel = "e", double = true }
+//  d = { arc = "big", dots = 3 }
+//  qu = { arc = "on", lines = 1 }
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Table {
+    letters: BTreeMap<String,Entry>,//sorted, so the first bad entry is always the same one
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    vowel: Option<String>,
+    #[serde(default)]
+    double: bool,
+    arc: Option<String>,
+    dots: Option<i32>,
+    lines: Option<i32>,
+}
+
+fn invalid(reason:String) -> GallifreyanError {
+    GallifreyanError::InvalidAlphabet{reason}
+}
+
+fn vowel(name:&str) -> Option<Vowels> {
+    match name {
+        "a" => Some(A),
+        "e" => Some(E),
+        "i" => Some(I),
+        "o" => Some(O),
+        "u" => Some(U),
+        _ => None
+    }
+}
+
+fn arc(name:&str) -> Option<Arc> {
+    match name {
+        "big" => Some(Big),
+        "above" => Some(Above),
+        "small" => Some(Small),
+        "on" => Some(On),
+        _ => None
+    }
+}
+
+fn letter(spelling:&str, entry:&Entry) -> Result<Letter,String> {
+    match (&entry.vowel, &entry.arc) {
+        (Some(v), None) => {
+            if entry.dots.is_some() || entry.lines.is_some() {
+                return Err(format!("{:#?} is a vowel, so can't have dots or lines", spelling));
+            }
+            let v = vowel(v).ok_or(format!("{:#?} has vowel {:#?}, expected a, e, i, o or u", spelling, v))?;
+            return Ok(VOpt(Vowel{v, double:entry.double}));
+        },
+        (None, Some(a)) => {
+            if entry.double {
+                return Err(format!("{:#?} is a consonant, only vowels can be double", spelling));
+            }
+            let a = arc(a).ok_or(format!("{:#?} has arc {:#?}, expected big, above, small or on", spelling, a))?;
+            let marks = match (entry.dots, entry.lines) {
+                (None, None) => None,
+                (Some(n), None) if (1..=4).contains(&n) => Some(Dot(n)),
+                (None, Some(n)) if (1..=3).contains(&n) => Some(Line(n)),
+                (Some(_), None) => return Err(format!("{:#?} must have from 1 to 4 dots", spelling)),
+                (None, Some(_)) => return Err(format!("{:#?} must have from 1 to 3 lines", spelling)),
+                (Some(_), Some(_)) => return Err(format!("{:#?} can have dots or lines, not both", spelling)),
+            };
+            return Ok(get_c(a, marks));
+        },
+        _ => Err(format!("{:#?} needs either a vowel or an arc", spelling)),
+    }
+}
+
+impl TryFrom<Table> for Alphabet {
+    type Error = GallifreyanError;
+    fn try_from(table:Table) -> Result<Self, Self::Error> {
+        if table.letters.is_empty() {
+            return Err(invalid("there are no letters".to_string()));
+        }
+        let mut singles = HashMap::new();
+        let mut doubles = HashMap::new();
+        for (spelling,entry) in &table.letters {
+            let chars:Vec<char> = spelling.chars().collect();
+            if !chars.iter().all(|c| c.is_ascii_lowercase()) {
+                return Err(invalid(format!("{:#?} can only be spelt with the letters a to z", spelling)));
+            }
+            let letter = letter(spelling, entry).map_err(invalid)?;
+            match chars[..] {
+                [c] => {singles.insert(c, letter);},
+                [a,b] => {doubles.insert((a,b), letter);},
+                _ => return Err(invalid(format!("{:#?} must be one or two letters long", spelling))),
+            }
+        }
+        return Ok(Self{singles, doubles});
+    }
+}
+
+fn line_and_column(text:&str, byte:usize) -> (usize,usize) {
+    let before = &text[..byte.min(text.len())];
+    let line = before.matches('\n').count()+1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count()+1;
+    return (line,column);
+}
+
+impl Alphabet {
+    pub fn from_toml(text:&str) -> Result<Self,GallifreyanError> {
+        let table:Table = toml::from_str(text).map_err(|e| {
+            let place = e.span().map(|s| line_and_column(text, s.start));
+            match place {
+                Some((line,column)) => invalid(format!("line {line}, column {column}: {}", e.message().trim())),
+                None => invalid(e.message().trim().to_string()),
+            }
+        })?;
+        return Self::try_from(table);
+    }
+    pub fn from_json(text:&str) -> Result<Self,GallifreyanError> {
+        let table:Table = serde_json::from_str(text).map_err(|e| invalid(e.to_string()))?;
+        return Self::try_from(table);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversion::parse_with_alphabet;
+
+    #[test]
+    fn example_is_sherman() {
+        let alphabet = Alphabet::from_toml(include_str!("../alphabets/sherman.toml")).unwrap();
+        assert_eq!(&alphabet, Alphabet::sherman());
+    }
+
+    #[test]
+    fn variants() {
+        let toml = "[letters]\na = { vowel = \"a\" }\nc = { arc = \"above\", dots = 2 }\nt = { arc = \"small\" }\n";
+        let alphabet = Alphabet::from_toml(toml).unwrap();
+        assert_eq!(alphabet.single('c'), Alphabet::sherman().single('k'));
+        assert_eq!(parse_with_alphabet("cat", &alphabet).unwrap(), crate::conversion::parse("kat").unwrap());
+
+        let json = r#"{"letters": {"b": {"arc": "big"}, "oo": {"vowel": "o", "double": true}}}"#;
+        let alphabet = Alphabet::from_json(json).unwrap();
+        assert_eq!(alphabet.double('o','o'), Alphabet::sherman().double('o','o'));
+        assert!(parse_with_alphabet("bt", &alphabet).is_err());
+    }
+
+    #[test]
+    fn bad_tables() {
+        let bad = [
+            "",
+            "[letters]",
+            "[letters]\nb = { arc = \"huge\" }",
+            "[letters]\na = { vowel = \"y\" }",
+            "[letters]\na = { vowel = \"a\", dots = 1 }",
+            "[letters]\nb = { arc = \"big\", double = true }",
+            "[letters]\nb = { arc = \"big\", dots = 5 }",
+            "[letters]\nb = { arc = \"big\", lines = 0 }",
+            "[letters]\nb = { arc = \"big\", dots = 1, lines = 1 }",
+            "[letters]\nb = { }",
+            "[letters]\nb = { vowel = \"a\", arc = \"big\" }",
+            "[letters]\nB = { arc = \"big\" }",
+            "[letters]\nthe = { arc = \"big\" }",
+            "[letters]\nb = { arc = \"big\", colour = \"red\" }",
+            "[letters]\nb = { arc = \"big\" \n",
+        ];
+        for text in bad {
+            let error = Alphabet::from_toml(text).unwrap_err();
+            assert!(matches!(error, GallifreyanError::InvalidAlphabet{..}), "{:#?} gave {:#?}", text, error);
+        }
+        assert!(Alphabet::from_json("{\"letters\": {\"b\": {\"arc\": 1}}}").is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use gallifreyan::GallifreyanError;
+
+const MAX_NAME:usize = 60;
+
+#[derive(Debug,Clone)]
+pub struct Row {
+    pub line: usize,//where the row starts in the file, from 1
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct Failure {
+    pub row: Row,
+    pub reason: String,
+}
+
+#[derive(Debug,Default)]
+pub struct Report {
+    pub rendered: Vec<(Row,String)>,//and the file it went to
+    pub failed: Vec<Failure>,
+}
+
+impl Report {
+    pub fn summary(&self, out_dir:&Path) -> String {
+        let total = self.rendered.len()+self.failed.len();
+        let mut lines = vec![format!("rendered {} of {} rows into {}", self.rendered.len(), total, out_dir.display())];
+        for f in &self.failed {
+            lines.push(format!("line {} {:#?}: {}", f.row.line, f.row.text, f.reason));
+        }
+        return lines.join("\n");
+    }
+}
+
+fn split_csv(contents:&str) -> Vec<(usize,Vec<String>)> {//quoted fields may hold commas, doubled quotes and newlines
+    let mut records:Vec<(usize,Vec<String>)> = Vec::new();
+    let mut fields:Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            },
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            ('\r', false) => (),
+            ('\n', false) => {
+                fields.push(std::mem::take(&mut field));
+                records.push((start, std::mem::take(&mut fields)));
+                line += 1;
+                start = line;
+            },
+            _ => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((start, fields));
+    }
+    return records;
+}
+
+pub fn read_rows(contents:&str, csv:bool, column:usize, header:bool) -> Result<Vec<Row>,String> {//column counts from 1
+    if column == 0 {
+        return Err("columns are counted from 1".to_string());
+    }
+    let records:Vec<(usize,Vec<String>)> = if csv {
+        split_csv(contents)
+    } else {
+        contents.lines().enumerate().map(|(i,l)| (i+1, vec![l.to_string()])).collect()
+    };
+    let skip = if header {1} else {0};
+    let mut rows:Vec<Row> = Vec::new();
+    for (line,fields) in records.into_iter().skip(skip) {
+        if fields.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+        match fields.get(column-1) {
+            Some(text) => rows.push(Row{line, text:text.clone()}),
+            None => return Err(format!("line {} has no column {}", line, column)),
+        }
+    }
+    return Ok(rows);
+}
+
+pub fn sanitise(text:&str) -> String {//a file name that is safe everywhere
+    let mut name = String::new();
+    for c in text.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() || c == '-' {
+            name.push(c);
+        } else if !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let name:String = name.trim_matches('_').chars().take(MAX_NAME).collect();
+    let name = name.trim_end_matches('_');
+    if is_reserved(name) {
+        return format!("{name}_");
+    }
+    return name.to_string();
+}
+
+fn is_reserved(name:&str) -> bool {//windows won't make these files, whatever the extension
+    match name {
+        "con"|"prn"|"aux"|"nul" => true,
+        _ => (name.starts_with("com") || name.starts_with("lpt")) && name.len() == 4 && name.ends_with(|c:char| ('1'..='9').contains(&c)),
+    }
+}
+
+fn unique_name(text:&str, line:usize, used:&mut HashSet<String>) -> String {
+    let mut base = sanitise(text);
+    if base.is_empty() {
+        base = format!("line_{line}");
+    }
+    let mut name = base.clone();
+    let mut i = 2;
+    while used.contains(&name) {
+        name = format!("{base}_{i}");
+        i += 1;
+    }
+    used.insert(name.clone());
+    return name;
+}
+
+pub fn run<F>(rows:Vec<Row>, out_dir:&Path, extension:&str, render:F) -> io::Result<Report>
+where F: Fn(&str) -> Result<Vec<u8>,GallifreyanError> {
+    fs::create_dir_all(out_dir)?;
+    let mut used:HashSet<String> = HashSet::new();
+    let mut report = Report::default();
+    for row in rows {
+        match render(&row.text) {
+            Ok(bytes) => {
+                let file = format!("{}.{}", unique_name(&row.text, row.line, &mut used), extension);
+                fs::write(out_dir.join(&file), bytes)?;
+                report.rendered.push((row, file));
+            },
+            Err(e) => report.failed.push(Failure{row, reason:e.to_string()}),
+        }
+    }
+    return Ok(report);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(rows:&[Row]) -> Vec<(usize,&str)> {
+        rows.iter().map(|r| (r.line, r.text.as_str())).collect()
+    }
+
+    #[test]
+    fn csv() {
+        let contents = "text,note\r\n\"hello, world\",1\r\n\"she said \"\"hi\"\"\",2\r\n\"two\nlines\",3\r\n\r\nlast,4";
+        let rows = read_rows(contents, true, 1, true).unwrap();
+        assert_eq!(texts(&rows), vec![(2, "hello, world"), (3, "she said \"hi\""), (4, "two\nlines"), (7, "last")]);
+        let rows = read_rows(contents, true, 2, false).unwrap();
+        assert_eq!(texts(&rows), vec![(1, "note"), (2, "1"), (3, "2"), (4, "3"), (7, "4")]);
+
+        assert_eq!(read_rows("a,b\nc", true, 2, false).unwrap_err(), "line 2 has no column 2");
+        assert!(read_rows("a", true, 0, false).is_err());
+    }
+
+    #[test]
+    fn lines() {
+        let rows = read_rows("hello\r\n\n  \nworld, again\n", false, 1, false).unwrap();
+        assert_eq!(texts(&rows), vec![(1, "hello"), (4, "world, again")]);
+        let rows = read_rows("title\nhello", false, 1, true).unwrap();
+        assert_eq!(texts(&rows), vec![(2, "hello")]);
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(sanitise("  Hello, World! "), "hello_world");
+        assert_eq!(sanitise("don't-stop"), "don_t-stop");
+        assert_eq!(sanitise("?!"), "");
+        assert_eq!(sanitise(&"a".repeat(100)).len(), MAX_NAME);
+        assert_eq!(sanitise("Con"), "con_");
+        assert_eq!(sanitise("lpt1"), "lpt1_");
+        assert_eq!(sanitise("com10"), "com10");
+        assert_eq!(sanitise("console"), "console");
+
+        let mut used = HashSet::new();
+        assert_eq!(unique_name("Hello!", 1, &mut used), "hello");
+        assert_eq!(unique_name("hello", 2, &mut used), "hello_2");
+        assert_eq!(unique_name("HELLO", 3, &mut used), "hello_3");
+        assert_eq!(unique_name("...", 4, &mut used), "line_4");
+        assert_eq!(unique_name("line 4", 5, &mut used), "line_4_2");
+    }
+}
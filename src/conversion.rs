@@ -1,11 +1,38 @@
 
 use std::convert::TryFrom;
-use std::f64::consts::TAU;
-use crate::tree::{Sentence, Word};
-use crate::tree::Number;
-use crate::shape::{BShape, SENTENCE_RADIUS};
-use crate::shape::{Cart, Circle, Polar, Shapes};
-use crate::draw_word::draw_plain_word;
+use crate::alphabet::Alphabet;
+use crate::phonetic::{parse_phonemes, Notation};
+use crate::normalize::normalize;
+use crate::tree::{read_document, Document};
+use crate::shape::{BShape, Cart};
+use crate::draw_document::{draw_document, Layout};
+use crate::error::{GallifreyanError, Offset, Warning};
+use crate::options::{ParseOptions, RenderOptions};
+use crate::raster::{PngOptions, Raster};
+use crate::vector::{PageOptions, Vector};
+use crate::svg::{escape, metadata, SvgWriter, NAMESPACE};
+use crate::decode::decode_text;
+use wasm_bindgen::prelude::*;
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug,Clone,PartialEq)]
+pub struct WarningReport {//a warning as the page sees it
+    pub char: usize,
+    pub byte: usize,
+    pub message: String,
+}
+impl From<&Warning> for WarningReport {
+    fn from(warning:&Warning) -> Self {
+        Self { char: warning.offset.char, byte: warning.offset.byte, message: warning.to_string() }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug,Clone,PartialEq)]
+pub struct Rendered {//the svg, or the error paragraph, along with whatever was skipped or replaced to draw it
+    pub svg: String,
+    pub warnings: Vec<WarningReport>,
+}
+
 pub struct Svg(String);
 impl Svg {
     pub fn svg(self) -> String { self.0 }
@@ -13,30 +40,137 @@ impl Svg {
 
 
 
+fn clean_input(value: &str, options: &ParseOptions) -> (Vec<char>,Vec<Offset>) {//normalize, lowercase and trim, remembering where each char came from
+    let mut chars:Vec<char> = Vec::new();
+    let mut origins:Vec<Offset> = Vec::new();
+    for (char_index,(byte_index,c)) in value.char_indices().enumerate() {
+        for l in normalize(c, options.accents).chars().flat_map(char::to_lowercase) {
+            chars.push(l);
+            origins.push(Offset::new(byte_index,char_index));
+        }
+    }
+    let start = chars.iter().position(|c| !c.is_whitespace()).unwrap_or(chars.len());
+    let end = chars.iter().rposition(|c| !c.is_whitespace()).map_or(start, |e| e+1);
+    return (chars[start..end].to_vec(), origins[start..end].to_vec());
+}
+
+pub fn parse(text: &str) -> Result<Document, GallifreyanError> {
+    return parse_with_alphabet(text, Alphabet::sherman());
+}
+
+pub fn parse_with_alphabet(text: &str, alphabet: &Alphabet) -> Result<Document, GallifreyanError> {
+    return parse_with_options(text, alphabet, &ParseOptions::default());
+}
+
+pub fn parse_with_options(text: &str, alphabet: &Alphabet, options: &ParseOptions) -> Result<Document, GallifreyanError> {
+    return parse_with_warnings(text, alphabet, options).map(|(document,_)| document);
+}
+
+pub fn parse_with_warnings(text: &str, alphabet: &Alphabet, options: &ParseOptions) -> Result<(Document,Vec<Warning>), GallifreyanError> {//along with whatever options.unknown skipped or replaced
+    let (input,origins) = clean_input(text, options);
+    let mut warnings = Vec::new();
+    let document = read_document(input, alphabet, options, &mut warnings).map_err(|e| e.locate(&origins))?;
+    let warnings = warnings.into_iter().map(|w| w.locate(&origins)).collect();
+    return Ok((document,warnings));
+}
+
+pub fn render(document: &Document, layout: Layout) -> Result<String, GallifreyanError> {
+    let opts = RenderOptions{layout, ..RenderOptions::default()};
+    return render_with_options(document, &opts);
+}
+
+pub fn render_with_options(document: &Document, opts: &RenderOptions) -> Result<String, GallifreyanError> {
+    return Ok(Svg::try_from((document,opts))?.svg());
+}
+
+pub fn render_with_source(document: &Document, text: &str, alphabet: &Alphabet, opts: &RenderOptions) -> Result<String, GallifreyanError> {//text is what the document was parsed from, kept in the metadata, and the alphabet it was parsed with spells the labels
+    return Ok(Svg::try_from((document,text,alphabet,opts))?.svg());
+}
+
+pub fn render_with_warnings(text: &str, alphabet: &Alphabet, parse: &ParseOptions, opts: &RenderOptions) -> Result<(String,Vec<Warning>), GallifreyanError> {
+    let (document,warnings) = parse_with_warnings(text, alphabet, parse)?;
+    return Ok((render_with_source(&document, text, alphabet, opts)?,warnings));
+}
+
+fn draw_sized(document: &Document, opts: &RenderOptions) -> Result<(BShape,Cart), GallifreyanError> {
+    let (document,size):(BShape,Cart) = draw_document(document,Alphabet::sherman(),opts)?;//these formats have no labels to spell
+    let size = if size.x <= 0.0 || size.y <= 0.0 {Cart::new(10.0,10.0)} else {size};//same as an empty svg
+    return Ok((document,size));
+}
+
+pub fn render_pdf(document: &Document, opts: &RenderOptions, page: &PageOptions) -> Result<Vec<u8>, GallifreyanError> {
+    let (document,size) = draw_sized(document,opts)?;
+    let mut vector = Vector::new();
+    document.draw(&mut vector);
+    return Ok(vector.to_pdf(size, page));
+}
+
+pub fn render_eps(document: &Document, opts: &RenderOptions, page: &PageOptions) -> Result<String, GallifreyanError> {
+    let (document,size) = draw_sized(document,opts)?;
+    let mut vector = Vector::new();
+    document.draw(&mut vector);
+    return Ok(vector.to_eps(size, page));
+}
+
+pub fn render_png(document: &Document, opts: &RenderOptions, png: &PngOptions) -> Result<Vec<u8>, GallifreyanError> {
+    let (document,size) = draw_sized(document,opts)?;
+    let mut raster = Raster::new(size, png.size);
+    document.draw(&mut raster);
+    return Ok(raster.to_png(png));
+}
+
 impl TryFrom<String> for Svg {
-    type Error = String;
+    type Error = GallifreyanError;
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let input = value.trim().to_lowercase().chars().collect::<Vec<char>>();
-        if input.is_empty() {
-            return Ok(Svg("<svg id=\"generated_svg\" viewBox=\"0 0 10 10\" version=\"1.1\" xmlns=\"https://github.com/D-G-Tomlinson/Gallifreyan\"></svg>".to_string()));
+        Svg::try_from((value,&RenderOptions::default()))
+    }
+}
+
+impl TryFrom<(String,&RenderOptions)> for Svg {
+    type Error = GallifreyanError;
+    fn try_from((value,opts): (String,&RenderOptions)) -> Result<Self, Self::Error> {
+        let document = &parse(&value)?;
+        Svg::try_from((document,value.as_str(),opts))
+    }
+}
+
+impl TryFrom<(&Document,&RenderOptions)> for Svg {
+    type Error = GallifreyanError;
+    fn try_from((document,opts): (&Document,&RenderOptions)) -> Result<Self, Self::Error> {
+        let text = document.to_string();
+        Svg::try_from((document,text.as_str(),opts))
+    }
+}
+
+impl TryFrom<(&Document,&str,&RenderOptions)> for Svg {//the text is only used for the metadata
+    type Error = GallifreyanError;
+    fn try_from((document,text,opts): (&Document,&str,&RenderOptions)) -> Result<Self, Self::Error> {
+        Svg::try_from((document,text,Alphabet::sherman(),opts))
+    }
+}
+
+impl TryFrom<(&Document,&str,&Alphabet,&RenderOptions)> for Svg {
+    type Error = GallifreyanError;
+    fn try_from((document,text,alphabet,opts): (&Document,&str,&Alphabet,&RenderOptions)) -> Result<Self, Self::Error> {
+        let head = if opts.metadata {metadata(text, document, alphabet)} else {String::new()};
+        if document.sentences().next().is_none() {
+            return Ok(Svg(format!("<svg id=\"generated_svg\" viewBox=\"0 0 10 10\" version=\"1.1\" xmlns=\"{NAMESPACE}\">{head}</svg>")));
         }
 
-        let sentence = &Sentence::try_from(input.into_iter().collect::<Vec<char>>())?;
-        let sen_rad=SENTENCE_RADIUS;
-        let mut sentence:BShape = (sentence,sen_rad).try_into()?;
-        let length = sen_rad * 2.0 * 1.1;
-        let half_length = Cart::new(length/2.0,length/2.0);
-        sentence.shove(half_length);
-        let els = sentence.to_element();
+        let (document,size):(BShape,_) = draw_document(document,alphabet,opts)?;
+        let (width,height) = (size.x,size.y);
+        let mut writer = SvgWriter::new(opts);
+        document.draw(&mut writer);
+        let els = writer.finish();
 
 
-        let mut start =
+        let start =
             format!("<svg
-  viewBox=\"0 0 {length} {length}\"
+  viewBox=\"0 0 {width} {height}\"
   version=\"1.1\"
   xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\"
   id=\"generated_svg\">
-    <g id=\"all_gall\">
+{head}    <g id=\"all_gall\">
 {els}
     </g>
 </svg>");
@@ -45,9 +179,79 @@ impl TryFrom<String> for Svg {
         return Ok(result);
     }
 }
+fn error_html(error: &GallifreyanError) -> String {//where it went wrong is kept in attributes, for the page to point at
+    return format!("<p class=\"error\" data-char=\"{}\" data-byte=\"{}\">{}</p>", error.offset().char, error.offset().byte, escape(&error.to_string()));
+}
 pub fn get_image(text: &str) -> String {
-    return match Svg::try_from(text.to_string()) {
+    return get_image_with_options(text, &RenderOptions::default());
+}
+pub fn get_image_with_layout(text: &str, layout: Layout) -> String {
+    let opts = RenderOptions{layout, ..RenderOptions::default()};
+    return get_image_with_options(text, &opts);
+}
+pub fn get_image_with_options(text: &str, opts: &RenderOptions) -> String {
+    return match Svg::try_from((text.to_string(),opts)) {
         Ok(image) => image.svg(),
-        Err(error) => format!("<p>{}</p>",error.to_string())
+        Err(error) => error_html(&error)
+    }
+}
+pub fn get_image_with_alphabet(text: &str, alphabet: &str, opts: &RenderOptions) -> String {
+    let image = Alphabet::from_json(alphabet)
+        .and_then(|alphabet| Svg::try_from((&parse_with_alphabet(text, &alphabet)?,text,&alphabet,opts)));
+    return match image {
+        Ok(image) => image.svg(),
+        Err(error) => error_html(&error)
+    }
+}
+pub fn get_image_with_parse_options(text: &str, parse: &ParseOptions, opts: &RenderOptions) -> Rendered {
+    return match render_with_warnings(text, Alphabet::sherman(), parse, opts) {
+        Ok((svg,warnings)) => Rendered{svg, warnings: warnings.iter().map(WarningReport::from).collect()},
+        Err(error) => Rendered{svg: error_html(&error), warnings: Vec::new()},
+    }
+}
+pub fn get_image_from_phonemes(text: &str, notation: Notation, opts: &RenderOptions) -> String {
+    let image = parse_phonemes(text, notation).and_then(|document| Svg::try_from((&document,text,opts)));
+    return match image {
+        Ok(image) => image.svg(),
+        Err(error) => error_html(&error)
+    }
+}
+pub fn get_text(svg: &str) -> String {
+    return match decode_text(svg) {
+        Ok(text) => text,
+        Err(error) => error_html(&error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalize::Accents;
+    use crate::options::Unknown;
+
+    #[test]
+    fn errors() {
+        //ë is two bytes, so everything after it is one more byte along than chars
+        assert_eq!(parse("zoë@"), Err(GallifreyanError::InvalidCharacter{character:'@', offset:Offset::new(4,3)}));
+        assert_eq!(parse("  zoë!\n1/0"), Err(GallifreyanError::InvalidNumber{reason:"a fraction must be over a number above zero".to_string(), offset:Offset::new(9,8)}));
+        let strict = ParseOptions{accents:Accents::Error, ..ParseOptions::default()};
+        assert_eq!(parse_with_options("zoë!", Alphabet::sherman(), &strict), Err(GallifreyanError::InvalidCharacter{character:'ë', offset:Offset::new(2,2)}));
+        assert_eq!(parse("zoë 1.2.3"), Err(GallifreyanError::InvalidNumber{reason:"it has more than one decimal point".to_string(), offset:Offset::new(8,7)}));
+
+        let html = get_image("zoë@");
+        assert!(html.starts_with("<p class=\"error\" data-char=\"3\" data-byte=\"4\">"), "{html}");
+        assert_eq!(get_text("<svg"), error_html(&decode_text("<svg").unwrap_err()));
+    }
+
+    #[test]
+    fn warnings() {
+        let skip = ParseOptions{unknown:Unknown::Skip, ..ParseOptions::default()};
+        let rendered = get_image_with_parse_options("zoë@ hi", &skip, &RenderOptions::default());
+        assert!(rendered.svg.starts_with("<svg") && rendered.svg.ends_with("</svg>"));
+        assert_eq!(rendered.warnings, vec![WarningReport{char:3, byte:4, message:"'@' at position 3 is not a valid letter, so it was skipped".to_string()}]);
+
+        let rendered = get_image_with_parse_options("zoë@ hi", &ParseOptions::default(), &RenderOptions::default());
+        assert_eq!(rendered.svg, error_html(&parse("zoë@ hi").unwrap_err()));
+        assert!(rendered.warnings.is_empty());
     }
 }
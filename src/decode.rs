@@ -0,0 +1,512 @@
+use crate::shape::{Arc, Cart, Circle, Line};
+use crate::tree::{Consonant, Digit, Document, Letter, Marks, Number, Paragraph, PunctuationTypes, Sentence, Suffix, Vowel, Vowels, Word, WordTypes};
+use crate::tree::PunctuationTypes::{NEnd, SEnd};
+use crate::tree::prefix;
+use crate::error::GallifreyanError;
+use crate::alphabet::Alphabet;
+use crate::xml::{self, Element};
+
+//reads back an svg drawn by this crate, using the classes on the groups and the shapes inside them
+
+enum Piece<'a> {
+    Group(&'a Element),
+    Circle(Circle),
+    Arc(Arc),
+    Line(Line),
+}
+
+struct Ring {//the circle a word or sentence sits on
+    centre: Cart,
+    radius: f64,
+}
+
+fn number(el:&Element, values:&[&str], i:usize) -> Result<f64,GallifreyanError> {
+    values.get(i).and_then(|v| v.parse().ok()).ok_or(el.error("path has too few numbers"))
+}
+
+fn attribute(el:&Element, name:&str) -> Result<f64,GallifreyanError> {
+    el.attribute(name).and_then(|v| v.trim().parse().ok()).ok_or(el.error(&format!("{name} is missing or not a number")))
+}
+
+fn path(el:&Element) -> Result<Piece<'_>,GallifreyanError> {
+    let d = el.attribute("d").ok_or(el.error("path has no d"))?.replace(',', " ");
+    let values:Vec<&str> = d.split_whitespace().collect();
+    let width = attribute(el, "stroke-width")?;
+    let start = Cart::new(number(el, &values, 1)?, number(el, &values, 2)?);
+    match (values.first(), values.get(3)) {
+        (Some(&"M"), Some(&"A")) => {
+            let end = Cart::new(number(el, &values, 9)?, number(el, &values, 10)?);
+            let large = number(el, &values, 7)? != 0.0;
+            let clockwise = number(el, &values, 8)? != 0.0;
+            Ok(Piece::Arc(Arc::new(start, end, number(el, &values, 4)?, large, clockwise, width)))
+        },
+        (Some(&"M"), Some(&"L")) => {
+            let end = Cart::new(number(el, &values, 4)?, number(el, &values, 5)?);
+            Ok(Piece::Line(Line::new(start, end, width, false)))
+        },
+        _ => Err(el.error("path is not an arc or a line")),
+    }
+}
+
+fn parts_of(el:&Element) -> Result<Vec<Piece<'_>>,GallifreyanError> {
+    let mut pieces:Vec<Piece> = Vec::new();
+    for e in el.elements() {
+        match e.name.as_str() {
+            "g" => pieces.push(Piece::Group(e)),
+            "circle" => {
+                let centre = Cart::new(attribute(e, "cx")?, attribute(e, "cy")?);
+                let filled = e.attribute("fill-opacity").is_none_or(|o| o.trim() != "0");
+                let thickness = if filled {None} else {Some(attribute(e, "stroke-width")?)};
+                pieces.push(Piece::Circle(Circle::new(centre, attribute(e, "r")?, thickness)));
+            },
+            "path" => pieces.push(path(e)?),
+            _ => (),//titles and the like
+        }
+    }
+    return Ok(pieces);
+}
+
+fn groups<'a>(pieces:&[Piece<'a>], class:&str) -> Vec<&'a Element> {
+    pieces.iter().filter_map(|p| match p {
+        Piece::Group(g) if g.has_class(class) => Some(*g),
+        _ => None,
+    }).collect()
+}
+fn circles<'a,'b>(pieces:&'b [Piece<'a>]) -> impl Iterator<Item=&'b Circle> {
+    pieces.iter().filter_map(|p| if let Piece::Circle(c) = p {Some(c)} else {None})
+}
+fn arcs<'a,'b>(pieces:&'b [Piece<'a>]) -> impl Iterator<Item=&'b Arc> {
+    pieces.iter().filter_map(|p| if let Piece::Arc(a) = p {Some(a)} else {None})
+}
+fn lines<'a,'b>(pieces:&'b [Piece<'a>]) -> impl Iterator<Item=&'b Line> {
+    pieces.iter().filter_map(|p| if let Piece::Line(l) = p {Some(l)} else {None})
+}
+
+fn vowel<F>(el:&Element, word:&Ring, places:F) -> Result<Vowel,GallifreyanError> where F: Fn(f64) -> [f64;3] {
+    //places gives how far from the word's centre an a, e and o of this radius would be
+    let pieces = parts_of(el)?;
+    let mut rings = circles(&pieces).filter(|c| c.thickness.is_some());//the filled ones are the ends of lines
+    let main = rings.next().ok_or(el.error("vowel has no circle"))?;
+    let double = rings.next().is_some();
+    let v = match lines(&pieces).next() {
+        Some(l) if l.end.distance(&word.centre) < word.radius => Vowels::I,
+        Some(_) => Vowels::U,
+        None => {
+            let distance = main.centre.distance(&word.centre);
+            let [a,e,o] = places(main.radius).map(|p| (p-distance).abs());
+            if a < e && a < o {Vowels::A} else if o < e {Vowels::O} else {Vowels::E}
+        }
+    };
+    return Ok(Vowel{v, double});
+}
+
+type Places = Box<dyn Fn(f64) -> [f64;3]>;//where an a, e and o of a radius would sit on a consonant, as vowel takes them
+
+fn consonant(el:&Element, word:&Ring) -> Result<Consonant,GallifreyanError> {
+    let pieces = parts_of(el)?;
+    let r = word.radius;
+    let outside = move |v:f64| r+1.1*v;
+    let big = arcs(&pieces).find(|a| a.clockwise && a.large);
+    let small = arcs(&pieces).find(|a| a.clockwise && !a.large);
+    let stroked = circles(&pieces).find(|c| c.thickness.is_some());
+    let (arc,places,caps):(crate::tree::Arc,Places,usize) = if let Some(b) = big {
+        let (centre,radius,_,_) = b.centre_and_angles();
+        let middle = centre.distance(&word.centre);
+        (crate::tree::Arc::Big, Box::new(move |v| [outside(v), middle, middle-radius]), 2)
+    } else if let Some(s) = small {
+        let (centre,radius,_,_) = s.centre_and_angles();
+        let inner = centre.distance(&word.centre)-radius;
+        (crate::tree::Arc::Small, Box::new(move |v| [outside(v), (inner+r)/2.0, inner]), 2)
+    } else if let Some(c) = stroked {
+        let (middle,radius) = (c.centre.distance(&word.centre), c.radius);
+        if (middle-r).abs() < radius*0.55 {
+            (crate::tree::Arc::On, Box::new(move |_| [r+radius/2.0, r, r-radius]), 0)
+        } else {
+            (crate::tree::Arc::Above, Box::new(move |v| [outside(v), middle, middle-radius]), 0)
+        }
+    } else {
+        return Err(el.error("consonant has no arc or circle"));
+    };
+    let num_lines = lines(&pieces).count() as i32;
+    let num_dots = circles(&pieces).filter(|c| c.thickness.is_none()).count().saturating_sub(caps) as i32;
+    let marks = if num_lines > 0 {
+        Some(Marks::Line(num_lines))
+    } else if num_dots > 0 {
+        Some(Marks::Dot(num_dots))
+    } else {
+        None
+    };
+    let diacritic = match groups(&pieces, "vowel").first() {
+        Some(v) => Some(vowel(v, word, places)?),
+        None => None,
+    };
+    return Ok(Consonant{arc, marks, diacritic});
+}
+
+fn word(el:&Element, alphabet:&Alphabet) -> Result<Word,GallifreyanError> {
+    let pieces = parts_of(el)?;
+    let connector = arcs(&pieces).next().ok_or(el.error("word has no arcs joining its letters"))?;
+    let (centre,radius,_,_) = connector.centre_and_angles();
+    let ring = Ring{centre, radius};
+    let mut letters:Vec<Letter> = Vec::new();
+    let parts = pieces.iter().filter_map(|p| match p {
+        Piece::Group(g) if g.has_class("letter") || g.has_class("apostrophe") => Some(*g),
+        _ => None,
+    });
+    for l in parts {
+        let letter = if l.has_class("apostrophe") {
+            Letter::Apostrophe
+        } else if l.has_class("consonant") {
+            Letter::COpt(consonant(l, &ring)?)
+        } else {
+            let v = groups(&parts_of(l)?, "vowel").first().copied().ok_or(l.error("letter has no consonant or vowel"))?;
+            let r = ring.radius;
+            Letter::VOpt(vowel(v, &ring, |v| [r+1.01*v, r, r-1.01*v])?)
+        };
+        if letter.text(alphabet).is_none() {
+            return Err(l.error("shapes do not make a letter"));
+        }
+        letters.push(letter);
+    }
+    return Ok(Word::from(letters));
+}
+
+fn ring_width(el:&Element) -> Result<f64,GallifreyanError> {
+    circles(&parts_of(el)?).find_map(|c| c.thickness).ok_or(el.error("digit has no ring"))
+}
+
+fn number_word(el:&Element) -> Result<Number,GallifreyanError> {
+    let pieces = parts_of(el)?;
+    let digit_groups = groups(&pieces, "digit");
+    let normal = ring_width(digit_groups.first().ok_or(el.error("number has no digits"))?)?;
+    let is_thick = |width:f64| width > normal*1.5;
+    let mut digits:Vec<Digit> = Vec::new();
+    let mut after:Vec<Digit> = Vec::new();//the digits inside the suffix's ring
+    let mut suffix:Option<&Element> = None;
+    let mut base = 10;
+    let mut was_comma = false;//a separator is drawn just before the digit it comes before
+    for p in &pieces {
+        let Piece::Group(d) = p else {continue};
+        if d.has_class("base") {//a tick for each digit the base has
+            base = lines(&parts_of(d)?).count() as u8;
+            if prefix(base).is_empty() {//any other base couldn't be written back down
+                return Err(d.error("base must be 2, 8 or 16"));
+            }
+            continue;
+        }
+        if d.has_class("fraction") || d.has_class("exponent") || d.has_class("percent") {
+            suffix = Some(d);
+            continue;
+        }
+        if d.has_class("separator") {
+            was_comma = true;
+            continue;
+        }
+        if !d.has_class("digit") {
+            continue;
+        }
+        let parts = parts_of(d)?;
+        let num_lines = lines(&parts).count() as u8;
+        if num_lines > 4 {
+            return Err(d.error("digit has more than four lines"));
+        }
+        let num_circles = circles(&parts).filter(|c| c.thickness.is_none()).count() as u8;
+        if num_circles > 3 {
+            return Err(d.error("digit has more than three circles"));
+        }
+        let follows_comma = was_comma;
+        was_comma = false;
+        if num_circles*5+num_lines >= base {
+            return Err(d.error("digit is too big for the base"));
+        }
+        let digit = Digit{num_circles, num_lines, follows_dot:is_thick(ring_width(d)?), follows_comma};
+        if suffix.is_some() {after.push(digit)} else {digits.push(digit)}
+    }
+    let suffix = match suffix {
+        Some(s) if s.has_class("fraction") => Some(Suffix::Fraction(Number{is_positive:true, is_whole:true, base:10, digits:after, suffix:None})),
+        Some(s) if s.has_class("exponent") => {
+            let is_positive = lines(&parts_of(s)?).next().is_none();
+            Some(Suffix::Exponent(Number{is_positive, is_whole:true, base:10, digits:after, suffix:None}))
+        },
+        Some(s) if !after.is_empty() => return Err(s.error("nothing can come after a percent sign")),
+        Some(_) => Some(Suffix::Percent),
+        None => None,
+    };
+    let (is_positive,is_whole) = match pieces.last() {
+        Some(Piece::Circle(c)) => (true, c.thickness.is_none()),
+        Some(Piece::Group(g)) if !g.has_class("digit") => {
+            let line = lines(&parts_of(g)?).next().map(|l| l.thickness).ok_or(g.error("negative sign has no line"))?;
+            (false, is_thick(line))
+        },
+        _ => return Err(el.error("number has no centre")),
+    };
+    return Ok(Number{is_positive, is_whole, base, digits, suffix:suffix.map(Box::new)});
+}
+
+fn mark(el:&Element, sentence:&Ring) -> Result<PunctuationTypes,GallifreyanError> {
+    let pieces = parts_of(el)?;
+    let num_lines = lines(&pieces).count();
+    let stroked = circles(&pieces).filter(|c| c.thickness.is_some()).count();
+    let filled:Vec<&Circle> = circles(&pieces).filter(|c| c.thickness.is_none()).collect();
+    let result = match (num_lines, stroked, filled.len()) {
+        (1,_,_) => NEnd('"'),
+        (2,_,_) => NEnd('\''),
+        (3,_,_) => NEnd('-'),
+        (0,1,0) => SEnd('.'),
+        (0,2,0) => NEnd(':'),
+        (0,0,1) => {
+            //a comma fills the mark, a semicolon's dot is smaller
+            let size = sentence.radius-filled[0].centre.distance(&sentence.centre);
+            if filled[0].radius > size*0.7 {NEnd(',')} else {NEnd(';')}
+        },
+        (0,0,2) => SEnd('?'),
+        (0,0,3) => SEnd('!'),
+        _ => return Err(el.error("shapes do not make a punctuation mark")),
+    };
+    return Ok(result);
+}
+
+fn sentence(el:&Element, alphabet:&Alphabet) -> Result<Sentence,GallifreyanError> {
+    let pieces = parts_of(el)?;
+    let mut rings = circles(&pieces);
+    let (outer,inner) = match (rings.next(), rings.next()) {
+        (Some(o), Some(i)) => (o, i),
+        _ => return Err(el.error("sentence has no ring")),
+    };
+    //the rings sit two and a half normal widths apart just outside the sentence's radius
+    let normal = (outer.radius-inner.radius)/1.5;
+    let ring = Ring{centre:outer.centre, radius:outer.radius-2.0*normal};
+    let mut words:Vec<WordTypes> = Vec::new();
+    for p in &pieces {
+        let Piece::Group(g) = p else {continue};
+        if g.has_class("punctuation") {
+            let marks = groups(&parts_of(g)?, "mark").iter().map(|m| mark(m, &ring)).collect::<Result<Vec<_>,_>>()?;
+            words.push(WordTypes::Punctuation(marks));
+        } else if g.has_class("number") {
+            words.push(WordTypes::Number(number_word(g)?));
+        } else if g.has_class("word") {
+            words.push(WordTypes::PlainWord(word(g, alphabet)?));
+        } else {
+            return Err(g.error("group is not a word, number or punctuation"));
+        }
+    }
+    return Ok(Sentence{words});
+}
+
+fn find_sentences(el:&Element, alphabet:&Alphabet, sentences:&mut Vec<Sentence>) -> Result<(),GallifreyanError> {
+    for e in el.elements() {
+        if e.has_class("sentence") {
+            sentences.push(sentence(e, alphabet)?);
+        } else {
+            find_sentences(e, alphabet, sentences)?;
+        }
+    }
+    return Ok(());
+}
+
+fn find_paragraphs(el:&Element, alphabet:&Alphabet, paragraphs:&mut Vec<Paragraph>) -> Result<(),GallifreyanError> {
+    for e in el.elements() {
+        if e.has_class("paragraph") {
+            let mut sentences:Vec<Sentence> = Vec::new();
+            find_sentences(e, alphabet, &mut sentences)?;
+            paragraphs.push(Paragraph{sentences});
+        } else {
+            find_paragraphs(e, alphabet, paragraphs)?;
+        }
+    }
+    return Ok(());
+}
+
+fn document(root:&Element, alphabet:&Alphabet) -> Result<Document,GallifreyanError> {
+    if root.name != "svg" {
+        return Err(root.error("not an svg"));
+    }
+    let mut paragraphs:Vec<Paragraph> = Vec::new();
+    find_paragraphs(root, alphabet, &mut paragraphs)?;
+    if paragraphs.is_empty() {//drawn before sentences were grouped into paragraphs, or with nothing in it
+        let mut sentences:Vec<Sentence> = Vec::new();
+        find_sentences(root, alphabet, &mut sentences)?;
+        paragraphs.push(Paragraph{sentences});
+    }
+    return Ok(Document{paragraphs});
+}
+
+pub fn decode(svg:&str) -> Result<Document,GallifreyanError> {
+    return decode_with_alphabet(svg, Alphabet::sherman());
+}
+
+pub fn decode_with_alphabet(svg:&str, alphabet:&Alphabet) -> Result<Document,GallifreyanError> {//for svgs drawn with a letter table of their own
+    return document(&xml::parse(svg)?, alphabet);
+}
+
+pub fn decode_text(svg:&str) -> Result<String,GallifreyanError> {//the original text if it was embedded, otherwise what the shapes spell
+    return decode_text_with_alphabet(svg, Alphabet::sherman());
+}
+
+pub fn decode_text_with_alphabet(svg:&str, alphabet:&Alphabet) -> Result<String,GallifreyanError> {
+    let root = xml::parse(svg)?;
+    let document = document(&root, alphabet)?;
+    return Ok(match root.find("gallifreyan:text") {
+        Some(source) => source.text.clone(),
+        None => document.text(alphabet),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversion::{parse, parse_with_alphabet, render_with_options, render_with_source};
+    use crate::draw_document::Layout;
+    use crate::options::RenderOptions;
+
+    fn round_trip(text:&str, opts:&RenderOptions) -> Document {
+        let document = parse(text).unwrap();
+        let svg = render_with_options(&document, opts).unwrap();
+        let decoded = decode(&svg).unwrap();
+        assert_eq!(document, decoded, "{:#?} did not survive the round trip", text);
+        return decoded;
+    }
+
+    #[test]
+    fn words_round_trip() {
+        let tests = vec!["hello world", "the quick brown fox jumps over the lazy dog",
+                         "a e i o u aa ee ii oo uu", "b j t th", "ba be bi bo bu baa",
+                         "ja je ji jo ju tha the thi tho thu ta te ti to tu",
+                         "church phone whale shop went night ghost queen sing", "xylophone zq vowel",
+                         "bachelor doctorate"];
+        for t in tests {
+            let decoded = round_trip(t, &RenderOptions::default());
+            assert_eq!(decoded.to_string(), t);
+        }
+    }
+
+    #[test]
+    fn apostrophes_round_trip() {
+        for text in ["don't", "it's rock'n'roll", "o'clock y'all", "l'a"] {
+            let decoded = round_trip(text, &RenderOptions::default());
+            assert_eq!(decoded.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn paragraphs_round_trip() {
+        let text = "first one. and two\n\nsecond\n\nthird, 3 4";
+        for layout in [Layout::Grid, Layout::Row, Layout::Spiral] {
+            let decoded = round_trip(text, &RenderOptions{layout, ..RenderOptions::default()});
+            assert_eq!(decoded.paragraphs.len(), 3);
+            assert_eq!(decoded.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn numbers_round_trip() {
+        let text = "12 -3 4.5 -6.07 1234567890 0.50";
+        let decoded = round_trip(text, &RenderOptions::default());
+        assert_eq!(decoded.to_string(), text);
+        let decoded = round_trip("costs .5 or 5. or -.25!", &RenderOptions::default());
+        assert_eq!(decoded.to_string(), "costs 0.5 or 5. or -0.25!");
+        let text = "3/4 -6.02e23 1e-5 12.5% 1,234,567.5";
+        let decoded = round_trip(text, &RenderOptions::default());
+        assert_eq!(decoded.to_string(), text);
+        let text = "0x1f -0b1010 0o777 0xabc.d";
+        let decoded = round_trip(text, &RenderOptions::default());
+        assert_eq!(decoded.to_string(), text);
+
+        //a ring of five ticks can be drawn from inside the crate, but there is no way to write base five
+        let mut number = parse("0x4").unwrap().sentences().next().unwrap().words[0].clone();
+        let WordTypes::Number(n) = &mut number else { panic!("{:#?} is not a number", number) };
+        n.base = 5;
+        let svg = render_with_options(&Document::from(Sentence{words: vec![number]}), &RenderOptions::default()).unwrap();
+        assert!(matches!(decode(&svg), Err(GallifreyanError::InvalidSvg{..})));
+    }
+
+    #[test]
+    fn punctuation_round_trip() {
+        let decoded = round_trip("hello. what? yes! no - way ' so.?", &RenderOptions::default());
+        assert_eq!(decoded.to_string(), "hello. what? yes! no- way ' so.?");
+
+        let marks = [',', ';', ':', '"', '\'', '-', '.', '?', '!'].iter()
+            .map(|c| if ".?!".contains(*c) {SEnd(*c)} else {NEnd(*c)}).collect();
+        let sentence = Sentence{words: vec![WordTypes::Punctuation(marks), WordTypes::PlainWord(Word::try_from(vec!['h','i']).unwrap())]};
+        let document = Document::from(sentence);
+        let svg = render_with_options(&document, &RenderOptions::default()).unwrap();
+        assert_eq!(decode(&svg).unwrap(), document);
+    }
+
+    #[test]
+    fn options_round_trip() {
+        let text = "one two three. four 5.6 -7! eight";
+        for layout in [Layout::Grid, Layout::Row, Layout::Spiral] {
+            let mut opts = RenderOptions{layout, ..RenderOptions::default()};
+            round_trip(text, &opts);
+            opts.scale_strokes(2.5);
+            round_trip(text, &opts);
+        }
+        let opts = RenderOptions{sentence_radius:7.0, padding:2.0, word_radius_divisor:1.3, ..RenderOptions::default()};
+        round_trip(text, &opts);
+        round_trip("", &RenderOptions::default());
+    }
+
+    #[test]
+    fn accessible_round_trip() {
+        let opts = RenderOptions{accessible:true, titles:true, ..RenderOptions::default()};
+        let document = round_trip("shh 42? a", &opts);
+        let svg = render_with_options(&document, &opts).unwrap();
+        assert!(svg.contains("<g class=\"plainword word\" role=\"group\" aria-label=\"shh\"><title>shh</title>"));
+        assert!(svg.contains("role=\"img\" aria-label=\"sh\""));
+        assert!(svg.contains("<g class=\"anti_clockwise_number digit\" role=\"img\" aria-label=\"4\">"));
+        assert!(svg.contains("aria-label=\"question mark\""));
+    }
+
+    #[test]
+    fn metadata_round_trip() {
+        let text = "Hello World. Bye?";
+        let opts = RenderOptions{metadata:true, ..RenderOptions::default()};
+        let document = parse(text).unwrap();
+        let svg = render_with_source(&document, text, Alphabet::sherman(), &opts).unwrap();
+        assert!(svg.contains("<title>Hello World. Bye?</title>"));
+        assert!(svg.contains("<gallifreyan:letters>he·l·lo wo·r·l·d. b·ye?</gallifreyan:letters>"));
+        assert_eq!(decode(&svg).unwrap(), document);
+        assert_eq!(decode_text(&svg).unwrap(), text);
+
+        let spaced = "  Hello.\r\n\r\n\tWorld \n";
+        let svg = render_with_source(&parse(spaced).unwrap(), spaced, Alphabet::sherman(), &opts).unwrap();
+        assert!(svg.contains("<title>Hello.\r\n\r\n\tWorld</title>"));
+        assert!(svg.contains("<gallifreyan:text>  Hello.&#13;\n&#13;\n\tWorld \n</gallifreyan:text>"));
+        assert_eq!(decode_text(&svg).unwrap(), spaced);
+
+        let svg = render_with_options(&document, &RenderOptions::default()).unwrap();
+        assert!(!svg.contains("<metadata>"));
+        assert_eq!(decode_text(&svg).unwrap(), "hello world. bye?");
+    }
+
+    #[test]
+    fn custom_alphabet_round_trip() {
+        //sherman has nothing for a big arc with one dot
+        let alphabet = Alphabet::from_toml("[letters]\na = { vowel = \"a\" }\nk = { arc = \"big\" }\nx = { arc = \"big\", dots = 1 }").unwrap();
+        let text = "kax xa";
+        let document = parse_with_alphabet(text, &alphabet).unwrap();
+        assert_eq!(document.text(&alphabet), text);
+        assert_eq!(document.letter_sequence(&alphabet), "ka·x xa");
+
+        let opts = RenderOptions{metadata:true, accessible:true, ..RenderOptions::default()};
+        let svg = render_with_source(&document, text, &alphabet, &opts).unwrap();
+        assert!(svg.contains("<gallifreyan:letters>ka·x xa</gallifreyan:letters>"));
+        assert!(svg.contains("role=\"group\" aria-label=\"kax\""));
+        assert!(svg.contains("role=\"img\" aria-label=\"x\""));
+        assert!(svg.contains("role=\"img\" aria-label=\"xa\""));
+
+        assert!(matches!(decode(&svg), Err(GallifreyanError::InvalidSvg{..})));
+        assert_eq!(decode_with_alphabet(&svg, &alphabet).unwrap(), document);
+        let svg = render_with_source(&document, text, &alphabet, &RenderOptions::default()).unwrap();
+        assert_eq!(decode_text_with_alphabet(&svg, &alphabet).unwrap(), text);
+    }
+
+    #[test]
+    fn bad_svgs() {
+        assert!(matches!(decode("hello"), Err(GallifreyanError::InvalidSvg{..})));
+        assert!(matches!(decode("<svg><g class=\"sentence\">"), Err(GallifreyanError::InvalidSvg{..})));
+        let error = decode("<svg>\n  <g class=\"sentence\"></g></svg>").unwrap_err();
+        assert_eq!(error.offset().byte, 8);
+    }
+}
@@ -0,0 +1,156 @@
+use wasm_bindgen::prelude::*;
+use crate::shape::{BShape, Cart, Polar, ShapeSet, Shapes};
+use crate::tree::{Document, Paragraph};
+use crate::error::GallifreyanError;
+use crate::options::RenderOptions;
+use crate::alphabet::Alphabet;
+
+const GOLDEN_ANGLE:f64 = 2.399_963_229_728_653;//pi*(3-sqrt(5))
+
+#[wasm_bindgen]
+#[derive(Debug,Clone,Copy,PartialEq,Default)]
+pub enum Layout {
+    #[default]
+    Grid,
+    Row,
+    Spiral,
+}
+impl TryFrom<&str> for Layout {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "grid" => Ok(Layout::Grid),
+            "row" => Ok(Layout::Row),
+            "spiral" => Ok(Layout::Spiral),
+            _ => Err(format!("{:#?} is not a layout, expected grid, row or spiral", value)),
+        }
+    }
+}
+
+fn get_sentence_rad(num_words:u32, max_words:u32, opts:&RenderOptions) -> f64 {
+    //area grows with the number of words, so the biggest sentence keeps the usual radius
+    return opts.sentence_radius * (num_words.max(1) as f64 / max_words.max(1) as f64).sqrt();
+}
+
+fn get_centres(cells:&[f64], layout:Layout) -> Vec<Cart> {//cells are the padded radius around each sentence
+    let biggest = cells.iter().cloned().fold(0.0, f64::max);
+    match layout {
+        Layout::Row => {
+            let mut x = 0.0;
+            let mut last = 0.0;
+            cells.iter().map(|c| {
+                x += last + c;
+                last = *c;
+                Cart::new(x, 0.0)
+            }).collect()
+        },
+        Layout::Grid => {
+            let columns = (cells.len() as f64).sqrt().ceil().max(1.0) as usize;
+            let step = 2.0*biggest;
+            (0..cells.len()).map(|i| Cart::new((i%columns) as f64*step, (i/columns) as f64*step)).collect()
+        },
+        Layout::Spiral => {
+            //sunflower spiral, neighbours are never closer than the step
+            let step = 2.0*biggest;
+            (0..cells.len()).map(|i| Polar::new(step*(i as f64).sqrt(), i as f64*GOLDEN_ANGLE).into()).collect()
+        },
+    }
+}
+
+fn draw_paragraph(paragraph:&Paragraph, max_words:u32, top:f64, alphabet:&Alphabet, opts:&RenderOptions) -> Result<(BShape,Cart,Cart),GallifreyanError> {//also return the corners, the paragraph starts at top and goes down
+    let sentences = &paragraph.sentences;
+    let radii:Vec<f64> = sentences.iter().map(|s| get_sentence_rad(s.get_num_words(),max_words,opts)).collect();
+    let cells:Vec<f64> = radii.iter().map(|r| r*opts.padding).collect();
+    let centres = get_centres(&cells, opts.layout);
+    let above = centres.iter().zip(cells.iter()).map(|(c,cell)| c.y-cell).fold(f64::INFINITY, f64::min);
+    let down = Cart::new(0.0, top-above);
+
+    let mut min = Cart::new(f64::INFINITY, f64::INFINITY);
+    let mut max = Cart::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+    let mut shapes:Shapes = Vec::new();
+    for ((sentence,(radius,cell)),centre) in sentences.iter().zip(radii.iter().zip(cells.iter())).zip(centres.iter()) {
+        let centre = Cart::new(centre.x+down.x, centre.y+down.y);
+        let mut shape:BShape = (sentence,*radius,alphabet,opts).try_into()?;
+        shape.shove(centre);
+        shapes.push(shape);
+        min = Cart::new(min.x.min(centre.x-cell), min.y.min(centre.y-cell));
+        max = Cart::new(max.x.max(centre.x+cell), max.y.max(centre.y+cell));
+    }
+    let shape = Box::new(ShapeSet::new(shapes, "paragraph").with_label(paragraph.text(alphabet), "group"));
+    return Ok((shape, min, max));
+}
+
+pub fn draw_document(document:&Document, alphabet:&Alphabet, opts:&RenderOptions) -> Result<(BShape,Cart),GallifreyanError> {//also return the size, the alphabet spells the labels
+    //each paragraph is laid out on its own, then they are stacked top to bottom
+    let max_words = document.sentences().map(|s| s.get_num_words()).max().unwrap_or(0);
+
+    let mut min = Cart::new(f64::INFINITY, f64::INFINITY);
+    let mut max = Cart::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+    let mut shapes:Shapes = Vec::new();
+    let mut top = 0.0;
+    for paragraph in document.paragraphs.iter().filter(|p| !p.sentences.is_empty()) {
+        let (shape,p_min,p_max) = draw_paragraph(paragraph, max_words, top, alphabet, opts)?;
+        shapes.push(shape);
+        min = Cart::new(min.x.min(p_min.x), min.y.min(p_min.y));
+        max = Cart::new(max.x.max(p_max.x), max.y.max(p_max.y));
+        top = p_max.y;
+    }
+    if shapes.is_empty() {
+        min = Cart::origin();
+        max = Cart::origin();
+    }
+    let label = document.text(alphabet);
+    let mut document:BShape = Box::new(ShapeSet::new(shapes, "document").with_label(label, "group"));
+    document.shove(Cart::new(-min.x, -min.y));
+    return Ok((document, min.to(&max)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sentence_radius() {
+        let opts = RenderOptions::default();
+        assert_eq!(get_sentence_rad(9, 9, &opts), opts.sentence_radius);
+        assert_eq!(get_sentence_rad(0, 0, &opts), opts.sentence_radius);
+        assert!((get_sentence_rad(1, 4, &opts) - opts.sentence_radius/2.0).abs() < 1e-9);//a quarter of the words, a quarter of the area
+        let radii:Vec<f64> = (0..=9).map(|n| get_sentence_rad(n, 9, &opts)).collect();
+        assert_eq!(radii[0], radii[1]);//no words is drawn as big as one
+        assert!(radii[1..].windows(2).all(|r| r[0] < r[1]), "{:#?} don't grow with the words", radii);
+    }
+
+    #[test]
+    fn centres_dont_overlap() {
+        let cells:Vec<f64> = (0..40).map(|i| 10.0 + (i*7%11) as f64 * 3.0).collect();
+        for layout in [Layout::Row, Layout::Grid, Layout::Spiral] {
+            for n in 0..=cells.len() {
+                let centres = get_centres(&cells[..n], layout);
+                assert_eq!(centres.len(), n);
+                for i in 0..n {
+                    for j in 0..i {
+                        let gap = centres[i].distance(&centres[j]) - cells[i] - cells[j];
+                        assert!(gap > -1e-9, "{:?} overlaps cells {} and {} of {} by {}", layout, i, j, n, -gap);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn layouts() {
+        let cells = [10.0, 30.0, 20.0, 5.0, 25.0];
+        let row = get_centres(&cells, Layout::Row);
+        assert!(row.iter().all(|c| c.y == 0.0));
+        for (i,pair) in row.windows(2).enumerate() {//each sentence touches the one before
+            assert!((pair[1].x - pair[0].x - cells[i] - cells[i+1]).abs() < 1e-9);
+        }
+        let grid = get_centres(&cells, Layout::Grid);
+        assert_eq!(grid.iter().map(|c| (c.x/60.0, c.y/60.0)).collect::<Vec<_>>(), vec![(0.0,0.0), (1.0,0.0), (2.0,0.0), (0.0,1.0), (1.0,1.0)]);//three columns, a step of the biggest cell across
+        let spiral = get_centres(&cells, Layout::Spiral);
+        assert_eq!((spiral[0].x, spiral[0].y), (0.0, 0.0));
+        assert!(spiral.windows(2).all(|p| p[0].distance(&Cart::origin()) < p[1].distance(&Cart::origin())));//each one further out
+        assert_eq!(Layout::try_from(" Spiral "), Ok(Layout::Spiral));
+        assert!(Layout::try_from("circle").is_err());
+    }
+}
@@ -1,69 +1,191 @@
 use std::f64::consts::TAU;
-use crate::shape::{Polar, Cart, Circle, Line, Thickness, Shapes, Shape, ShapeSet, BShape};
+use crate::shape::{Polar, Cart, Circle, Line, Shapes, Shape, ShapeSet, BShape};
 use crate::shape::Thickness::*;
-use crate::tree::{Number, Word};
-use crate::tree::Digit;
+use crate::tree::{Number, Digit, Suffix};
+use crate::options::RenderOptions;
 
-impl From<(&Digit, f64,f64,bool,f64)> for BShape {
-    fn from((digit,outer,inner,is_clockwise,word_radius): (&Digit, f64,f64,bool,f64)) -> BShape {
+const SEPARATOR_SIZE:f64 = 0.3;//radius of a thousands separator's dot, in half ring widths
+
+impl From<(&Digit, f64,f64,bool,f64,&RenderOptions)> for BShape {
+    fn from((digit,outer,inner,is_clockwise,word_radius,opts): (&Digit, f64,f64,bool,f64,&RenderOptions)) -> BShape {
         let outer_ring_thickness = if digit.follows_dot {ExtraThick} else {Normal};
-        let mut shapes:Shapes =vec![Box::new(Circle::new(Cart::origin(),outer,Some(outer_ring_thickness.val(word_radius))))];
+        let mut shapes:Shapes =vec![Box::new(Circle::new(Cart::origin(),outer,Some(outer_ring_thickness.val(word_radius,opts))))];
 
         let mut current_pos = Polar::new((outer+inner)/2.0,-TAU/4f64);
         let half_dist = (outer-inner)/2.0;
-        let diff:f64;
-        if digit.has_circle {
+        let diff = TAU/(digit.num_circles+digit.num_lines) as f64;//circles first, then lines, spread evenly round the ring
+        for _ in 0..digit.num_circles {
             let circle = Circle::new(current_pos.into(),half_dist,None);
             shapes.push(Box::new(circle));
-            diff = TAU/(digit.num_lines+1) as f64;
             current_pos = current_pos.rotate(diff);
-        } else {
-            diff = TAU/digit.num_lines as f64;
         }
         for _ in 0..digit.num_lines {
             let start:Cart = current_pos.extend(-half_dist).into();
             let end:Cart = current_pos.extend(half_dist).into();
-            let line:Line = Line::new(start,end,Normal.val(word_radius),false);
+            let line:Line = Line::new(start,end,Normal.val(word_radius,opts),false);
             shapes.push(Box::new(line));
             current_pos = current_pos.rotate(diff);
         }
-        return Box::new(ShapeSet::new_rotating_class(shapes, is_clockwise, "digit"));
+        return Box::new(ShapeSet::new_rotating_class(shapes, is_clockwise, "digit").with_label(digit.text().to_string(), "img"));
     }
 }
 
-fn get_centre(is_whole:&bool,is_positive:&bool,current_inner:f64,is_clockwise:bool,word_radius:f64) -> BShape {
+fn draw_separator(outer:f64, inner:f64) -> BShape {//a dot on the outside of the ring, where the comma would be, left out of the digit's group so it doesn't throw the spin off centre
+    let dot = Circle::new(Polar::new(outer,TAU/4f64).into(),(outer-inner)/2.0*SEPARATOR_SIZE,None);
+    return Box::new(ShapeSet::new(vec![Box::new(dot)],"separator"));
+}
+
+fn draw_suffix(suffix:&Suffix, outer:f64, inner:f64, word_radius:f64, opts:&RenderOptions) -> BShape {//a ring like a digit's, with a mark for what the digits inside it mean
+    let middle = (outer+inner)/2.0;
+    let half_dist = (outer-inner)/2.0;
+    let mut shapes:Shapes = vec![Box::new(Circle::new(Cart::origin(),outer,Some(Normal.val(word_radius,opts))))];
+    let small = |theta:f64| -> Box<dyn Shape> {
+        Box::new(Circle::new(Polar::new(middle,theta).into(),half_dist*0.5,Some(Thin.val(word_radius,opts))))
+    };
+    let (class,label) = match suffix {
+        Suffix::Fraction(_) => {//a bar all the way round, the digits inside are underneath it
+            shapes.push(Box::new(Circle::new(Cart::origin(),middle,Some(Thin.val(word_radius,opts)))));
+            ("fraction","over")
+        },
+        Suffix::Exponent(power) => {//one small circle raised at the top, and a line at the bottom if the power is negative
+            shapes.push(small(-TAU/4f64));
+            if !power.is_positive {
+                let bottom = Polar::new(middle,TAU/4f64);
+                shapes.push(Box::new(Line::new(bottom.extend(-half_dist).into(),bottom.extend(half_dist).into(),Normal.val(word_radius,opts),false)));
+            }
+            ("exponent","times ten to the power of")
+        },
+        Suffix::Percent => {//two small circles, like the sign
+            shapes.push(small(-TAU/4f64));
+            shapes.push(small(TAU/4f64));
+            ("percent","percent")
+        },
+    };
+    return Box::new(ShapeSet::new(shapes,class).with_label(label.to_string(), "img"));
+}
+
+fn draw_base(base:u8, outer:f64, inner:f64, word_radius:f64, opts:&RenderOptions) -> BShape {//a ring round the outside with a tick for each digit the base has, like a dial
+    let half_dist = (outer-inner)/2.0;
+    let mut shapes:Shapes = vec![Box::new(Circle::new(Cart::origin(),outer,Some(Normal.val(word_radius,opts))))];
+    let mut tick = Polar::new(outer,-TAU/4f64);
+    for _ in 0..base {
+        shapes.push(Box::new(Line::new(tick.into(),tick.extend(-half_dist).into(),Thin.val(word_radius,opts),false)));
+        tick = tick.rotate(TAU/base as f64);
+    }
+    return Box::new(ShapeSet::new(shapes,"base").with_label(format!("base {base}"), "img"));
+}
+
+fn get_centre(is_whole:&bool,is_positive:&bool,current_inner:f64,is_clockwise:bool,word_radius:f64,opts:&RenderOptions) -> BShape {
     match (is_whole,is_positive) {
         (true,true) => Box::new(Circle::new(Cart::origin(),current_inner,None)),
         (true,false) => {
-            let circle = Circle::new(Cart::origin(),current_inner,Some(ExtraThick.val(word_radius)));
-            let line = Line::new(Cart::new(0.0,current_inner),Cart::new(0.0,-current_inner),ExtraThick.val(word_radius),false);
+            let circle = Circle::new(Cart::origin(),current_inner,Some(ExtraThick.val(word_radius,opts)));
+            let line = Line::new(Cart::new(0.0,current_inner),Cart::new(0.0,-current_inner),ExtraThick.val(word_radius,opts),false);
             Box::new(ShapeSet::new_rotating(vec![Box::new(circle), Box::new(line)], is_clockwise))
         },
-        (false,true) => Box::new(Circle::new(Cart::origin(),current_inner,Some(Normal.val(word_radius)))),
+        (false,true) => Box::new(Circle::new(Cart::origin(),current_inner,Some(Normal.val(word_radius,opts)))),
         (false,false) => {
-            let circle = Circle::new(Cart::origin(),current_inner,Some(Normal.val(word_radius)));
-            let line = Line::new(Cart::new(0.0,current_inner),Cart::new(0.0,-current_inner),Normal.val(word_radius),false);
+            let circle = Circle::new(Cart::origin(),current_inner,Some(Normal.val(word_radius,opts)));
+            let line = Line::new(Cart::new(0.0,current_inner),Cart::new(0.0,-current_inner),Normal.val(word_radius,opts),false);
             Box::new(ShapeSet::new_rotating(vec![Box::new(circle), Box::new(line)], is_clockwise))
         },
     }
 }
 
-impl From<(&Number,f64)> for BShape {
-    fn from((number,word_radius):(&Number,f64)) -> Self {
+enum Ring<'a> {//what each ring of a number holds, outside in
+    Base(u8),
+    Digit(&'a Digit),
+    Suffix(&'a Suffix),
+}
+
+impl From<(&Number,f64,&RenderOptions)> for BShape {
+    fn from((number,word_radius,opts):(&Number,f64,&RenderOptions)) -> Self {
         let mut shapes:Shapes = Shapes::new();
         let mut is_clockwise = false;
-        let delta_rad = word_radius/(number.digits.len() as f64 + 1f64);
+        let after:&[Digit] = match number.suffix.as_deref() {
+            Some(Suffix::Fraction(n)|Suffix::Exponent(n)) => &n.digits,
+            _ => &[],
+        };
+        //each digit gets a ring, with one round the outside for a base other than ten and one between the digits before and after a suffix
+        let rings:Vec<Ring> = Some(number.base).filter(|b| *b != 10).map(Ring::Base).into_iter()
+            .chain(number.digits.iter().map(Ring::Digit))
+            .chain(number.suffix.as_deref().map(Ring::Suffix))
+            .chain(after.iter().map(Ring::Digit))
+            .collect();
+        let delta_rad = word_radius/(rings.len() as f64 + 1f64);
         let mut current_inner = word_radius;
-        let mut current_outer = word_radius;
-        for d in &number.digits {
-            current_outer = current_inner;
+        for ring in rings {
+            let current_outer = current_inner;
             current_inner = current_inner - delta_rad;
-            let digit_shapes = (d,current_outer, current_inner, is_clockwise,word_radius).into();
-            shapes.push(digit_shapes);
-            is_clockwise = !is_clockwise;
+            match ring {
+                Ring::Base(base) => shapes.push(draw_base(base, current_outer, current_inner, word_radius, opts)),
+                Ring::Digit(d) => {
+                    if d.follows_comma {
+                        shapes.push(draw_separator(current_outer, current_inner));
+                    }
+                    let digit_shapes = (d,current_outer, current_inner, is_clockwise,word_radius,opts).into();
+                    shapes.push(digit_shapes);
+                    is_clockwise = !is_clockwise;
+                },
+                Ring::Suffix(suffix) => shapes.push(draw_suffix(suffix, current_outer, current_inner, word_radius, opts)),
+            }
+        }
+
+        shapes.push(get_centre(&number.is_whole,&number.is_positive,current_inner,is_clockwise,word_radius,opts));
+        return Box::new(ShapeSet::new(shapes,"word number").with_label(number.to_string(), "group"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::{Arc, Renderer};
+
+    #[derive(Default)]
+    struct Boxes {//the box round what each spinning group draws, which the webpage spins it about
+        open: Vec<Option<[f64;4]>>,//left, top, right, bottom of each group still open, none if it doesn't spin
+        found: Vec<[f64;4]>,
+    }
+
+    impl Boxes {
+        fn add(&mut self, left:f64, top:f64, right:f64, bottom:f64) {
+            for b in self.open.iter_mut().flatten() {
+                *b = [b[0].min(left), b[1].min(top), b[2].max(right), b[3].max(bottom)];
+            }
         }
+    }
 
-        shapes.push(get_centre(&number.is_whole,&number.is_positive,current_inner,is_clockwise,word_radius));
-        return Box::new(ShapeSet::new(shapes,"word number"));
+    impl Renderer for Boxes {
+        fn circle(&mut self, circle:&Circle) {
+            let c = circle.centre;
+            self.add(c.x-circle.radius, c.y-circle.radius, c.x+circle.radius, c.y+circle.radius);
+        }
+        fn arc(&mut self, _arc:&Arc) {}
+        fn line(&mut self, line:&Line) {
+            self.add(line.start.x.min(line.end.x), line.start.y.min(line.end.y), line.start.x.max(line.end.x), line.start.y.max(line.end.y));
+        }
+        fn begin_group(&mut self, group:&ShapeSet) {
+            let spins = group.class().contains("_number");
+            self.open.push(spins.then_some([f64::MAX, f64::MAX, f64::MIN, f64::MIN]));
+        }
+        fn end_group(&mut self, _group:&ShapeSet) {
+            if let Some(Some(b)) = self.open.pop() {
+                self.found.push(b);
+            }
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn spins_about_the_centre() {
+        for text in ["1,234,567", "-12,345.5", "1,000"] {
+            let number = Number::try_from(text.chars().collect::<Vec<_>>()).unwrap();
+            let shape:BShape = (&number, 50.0, &RenderOptions::default()).into();
+            let mut boxes = Boxes::default();
+            shape.draw(&mut boxes);
+            assert!(boxes.found.len() >= number.digits.len());
+            for [left,top,right,bottom] in boxes.found {
+                assert!((left+right).abs() < 1e-9 && (top+bottom).abs() < 1e-9, "{text} has a ring that spins about ({}, {})", (left+right)/2.0, (top+bottom)/2.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use crate::shape::{BShape, Cart, Circle, Line, Polar, ShapeSet, Shapes};
+use crate::shape::Thickness::*;
+use crate::tree::PunctuationTypes;
+use crate::tree::PunctuationTypes::{NEnd, SEnd};
+use crate::error::{GallifreyanError, Offset};
+use crate::options::RenderOptions;
+
+const MARK_SPACING:f64 = 3.5;//gap between neighbouring marks, in mark sizes
+
+fn mark_char(mark:&PunctuationTypes) -> char {
+    match mark {
+        NEnd(c)|SEnd(c) => *c
+    }
+}
+
+fn mark_name(mark:char) -> String {//what a screen reader should say
+    let name = match mark {
+        '.' => "full stop",
+        ',' => "comma",
+        ':' => "colon",
+        ';' => "semicolon",
+        '?' => "question mark",
+        '!' => "exclamation mark",
+        '"' => "quotation mark",
+        '\'' => "apostrophe",
+        '-' => "dash",
+        _ => return mark.to_string(),
+    };
+    return name.to_string();
+}
+
+fn draw_dots(num:u32, pos:Polar, size:f64) -> Shapes {//a row of dots hanging just inside the sentence ring
+    let step = size/pos.radius;
+    let mut dot = pos.extend(-size).rotate(-step*(num-1) as f64/2.0);
+    let mut shapes = Shapes::new();
+    for _ in 0..num {
+        shapes.push(Box::new(Circle::new(dot.into(),size*0.4,None)));
+        dot = dot.rotate(step);
+    }
+    return shapes;
+}
+
+fn draw_lines(num:u32, pos:Polar, size:f64, word_radius:f64,opts:&RenderOptions) -> Shapes {//radial lines running inwards from the sentence ring
+    let step = size/pos.radius;
+    let mut start = pos.rotate(-step*(num-1) as f64/2.0);
+    let mut shapes = Shapes::new();
+    for _ in 0..num {
+        let end = start.extend(-size*1.5);
+        shapes.push(Box::new(Line::new(start.into(),end.into(),Normal.val(word_radius,opts),true)));
+        start = start.rotate(step);
+    }
+    return shapes;
+}
+
+fn draw_mark(mark:char, pos:Polar, size:f64, word_radius:f64,opts:&RenderOptions) -> Result<Shapes,GallifreyanError> {
+    let centre:Cart = pos.extend(-size).into();
+    let shapes:Shapes = match mark {
+        '.' => vec![Box::new(Circle::new(centre,size,Some(Normal.val(word_radius,opts))))],
+        ',' => vec![Box::new(Circle::new(centre,size,None))],
+        ':' => vec![Box::new(Circle::new(centre,size,Some(Normal.val(word_radius,opts)))),
+                    Box::new(Circle::new(centre,size*0.5,Some(Thin.val(word_radius,opts))))],
+        ';' => draw_dots(1,pos,size),
+        '?' => draw_dots(2,pos,size),
+        '!' => draw_dots(3,pos,size),
+        '"' => draw_lines(1,pos,size,word_radius,opts),
+        '\'' => draw_lines(2,pos,size,word_radius,opts),
+        '-' => draw_lines(3,pos,size,word_radius,opts),
+        _ => return Err(GallifreyanError::InvalidPunctuation{character:mark, offset:Offset::default()}),
+    };
+    return Ok(shapes);
+}
+
+pub fn draw_punctuation(marks:&[PunctuationTypes], pos:Polar, size:f64, word_radius:f64,opts:&RenderOptions) -> Result<BShape,GallifreyanError> {
+    //pos is the point on the sentence ring halfway between the neighbouring words, the marks are spread along the ring around it
+    let step = size*MARK_SPACING/pos.radius;
+    let mut current = pos.rotate(-step*marks.len().saturating_sub(1) as f64/2.0);
+    let mut shapes = Shapes::new();
+    for (i,m) in marks.iter().enumerate() {
+        let mark = draw_mark(mark_char(m),current,size,word_radius,opts).map_err(|e| e.shift(Offset::new(i,i)))?;
+        shapes.push(Box::new(ShapeSet::new(mark,"mark").with_label(mark_name(mark_char(m)), "img")));
+        current = current.rotate(step);
+    }
+    return Ok(Box::new(ShapeSet::new(shapes,"punctuation")));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, TAU};
+    use crate::alphabet::Alphabet;
+    use crate::conversion::parse;
+    use crate::tree::{Sentence, Word, WordTypes::{PlainWord, Punctuation}};
+    use crate::shape::{Arc, Renderer};
+
+    struct Mark {
+        name: String,
+        circles: Vec<(Cart,f64,bool)>,//centre, radius and whether it is filled
+        lines: usize,
+    }
+
+    #[derive(Default)]
+    struct Marks {//everything drawn inside each mark group
+        open: Vec<bool>,//whether each group still open is a mark
+        found: Vec<Mark>,
+    }
+
+    impl Renderer for Marks {
+        fn circle(&mut self, circle:&Circle) {
+            if self.open.last() == Some(&true) && let Some(mark) = self.found.last_mut() {
+                mark.circles.push((circle.centre, circle.radius, circle.thickness.is_none()));
+            }
+        }
+        fn arc(&mut self, _arc:&Arc) {}
+        fn line(&mut self, _line:&Line) {
+            if self.open.last() == Some(&true) && let Some(mark) = self.found.last_mut() {
+                mark.lines += 1;
+            }
+        }
+        fn begin_group(&mut self, group:&ShapeSet) {
+            let is_mark = group.class() == "mark";
+            if is_mark {
+                self.found.push(Mark{name: group.label().unwrap_or_default().to_string(), circles: Vec::new(), lines: 0});
+            }
+            self.open.push(is_mark);
+        }
+        fn end_group(&mut self, _group:&ShapeSet) {
+            self.open.pop();
+        }
+    }
+
+    fn draw(marks:&[PunctuationTypes]) -> Result<Vec<Mark>,GallifreyanError> {
+        let mut found = Marks::default();
+        draw_punctuation(marks, Polar::new(100.0, FRAC_PI_2), 5.0, 50.0, &RenderOptions::default())?.draw(&mut found);
+        return Ok(found.found);
+    }
+
+    fn angle(point:Cart) -> f64 {//anticlockwise from the x axis, like a polar
+        (-point.y).atan2(point.x)
+    }
+
+    #[test]
+    fn every_mark() {
+        //name, circles, filled circles, lines
+        let tests = [('.', "full stop", 1, 0, 0), (',', "comma", 1, 1, 0), (':', "colon", 2, 0, 0),
+                     (';', "semicolon", 1, 1, 0), ('?', "question mark", 2, 2, 0), ('!', "exclamation mark", 3, 3, 0),
+                     ('"', "quotation mark", 1, 1, 1), ('\'', "apostrophe", 2, 2, 2), ('-', "dash", 3, 3, 3)];
+        for (c,name,circles,filled,lines) in tests {
+            for mark in [NEnd(c), SEnd(c)] {
+                let found = draw(&[mark]).unwrap();
+                assert_eq!(found.len(), 1);
+                assert_eq!(found[0].name, name);
+                assert_eq!(found[0].circles.len(), circles, "{c}");
+                assert_eq!(found[0].circles.iter().filter(|(_,_,f)| *f).count(), filled, "{c}");
+                assert_eq!(found[0].lines, lines, "{c}");
+                for (centre,radius,_) in &found[0].circles {
+                    assert!(centre.distance(&Cart::origin())+radius <= 100.0+1e-9, "{c} pokes out of the sentence");
+                    assert!(*radius > 0.0);
+                }
+            }
+        }
+        let found = draw(&[NEnd(','), SEnd('?'), NEnd('"')]).unwrap();
+        assert_eq!(found.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["comma", "question mark", "quotation mark"]);
+        assert!(angle(found[0].circles[0].0) < angle(found[2].circles[0].0));//in order the same way round as the words
+        assert_eq!(draw(&[NEnd('.'), NEnd('@')]).err(), Some(GallifreyanError::InvalidPunctuation{character:'@', offset:Offset::new(1,1)}));
+        assert!(draw(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn between_words() {
+        //word k of n sits at a quarter turn clockwise plus k nths of a turn, marks go halfway to the next, or half a gap before the first
+        for (text,n,after) in [("a, b", 2.0, 0.0), ("a b, c", 3.0, 1.0), ("\"a b", 2.0, -1.0)] {
+            let sentence = parse(text).unwrap().paragraphs[0].sentences[0].clone();
+            let shape:BShape = (&sentence, 100.0, Alphabet::sherman(), &RenderOptions::default()).try_into().unwrap();
+            let mut found = Marks::default();
+            shape.draw(&mut found);
+            let expected = -FRAC_PI_2+(after+0.5)*TAU/n;
+            let centre = found.found[0].circles[0].0;
+            let off = (angle(centre)-expected).rem_euclid(TAU);
+            assert!(off.min(TAU-off) < 0.1, "{text} has its mark at {} not {}", angle(centre), expected);
+        }
+        let word = |c:char| PlainWord(Word::try_from(vec![c]).unwrap());
+        let sentence = Sentence{words: vec![word('a'), word('b'), Punctuation(vec![NEnd(',')]), Punctuation(vec![NEnd(';')]), word('c')]};
+        let shape:BShape = (&sentence, 100.0, Alphabet::sherman(), &RenderOptions::default()).try_into().unwrap();
+        let mut found = Marks::default();
+        shape.draw(&mut found);
+        let expected = -FRAC_PI_2+1.5*TAU/3.0;//both between b and c
+        for mark in &found.found {
+            let off = (angle(mark.circles[0].0)-expected).rem_euclid(TAU);
+            assert!(off.min(TAU-off) < 0.1, "the {} is at {} not {}", mark.name, angle(mark.circles[0].0), expected);
+        }
+    }
+
+    #[test]
+    fn on_their_own() {
+        for text in ["?", "...", "!?:"] {
+            let sentence = parse(text).unwrap().paragraphs[0].sentences[0].clone();
+            let shape:BShape = (&sentence, 100.0, Alphabet::sherman(), &RenderOptions::default()).try_into().unwrap();
+            let mut found = Marks::default();
+            shape.draw(&mut found);
+            assert!(!found.found.is_empty());
+            for (_,radius,_) in found.found.iter().flat_map(|m| m.circles.iter()) {
+                assert!(*radius > 1.0, "{text} drew a mark {radius} across");
+            }
+        }
+    }
+}
@@ -1,65 +1,76 @@
 use std::f64::consts::TAU;
 use crate::draw_word::draw_plain_word;
+use crate::draw_punctuation::draw_punctuation;
 use crate::shape::{BShape, Cart, Circle, Polar, ShapeSet, Shapes,Thickness::*};
-use crate::tree::{Number, Sentence, Word,WordTypes,WordTypes::*};
+use crate::tree::{Sentence,WordTypes,WordTypes::*};
+use crate::error::GallifreyanError;
+use crate::options::RenderOptions;
+use crate::alphabet::Alphabet;
 
 
-fn get_word_rad(sen_rad:f64,num_words:u32) -> (f64, f64,f64){
-    let diff = TAU/num_words as f64;
+fn get_word_rad(sen_rad:f64,num_words:u32,opts:&RenderOptions) -> (f64, f64,f64){
+    let diff = TAU/num_words.max(1) as f64;
     let max_word_radius = match num_words {
         0 => 0.0,
         1 => sen_rad,
         _ => sen_rad/(1f64+(2f64/(1f64-diff.cos())).sqrt())
     };
 
-    let word_radius = max_word_radius/1.6;
+    let word_radius = max_word_radius/opts.word_radius_divisor;
     return (word_radius, max_word_radius,diff);
 }
-fn draw_word(word: &WordTypes, this:&Polar,last:&Polar,diff:f64,word_radius:f64) -> Result<(BShape,Polar),String> {
+fn draw_word(word: &WordTypes, this:&Polar,last:Option<&Polar>,diff:f64,(word_radius,sen_rad):(f64,f64),alphabet:&Alphabet,opts:&RenderOptions) -> Result<(BShape,Polar),GallifreyanError> {
     match word{
         PlainWord(word) => {
-            let word = Word::try_from(word.clone())?;
             let cart_pos:Cart = Cart::from(this.clone());
-            let mut these_shapes = draw_plain_word(&word, this,word_radius);
+            let mut these_shapes = draw_plain_word(word, word_radius,alphabet,opts);
             these_shapes.shove(cart_pos);
             Ok((these_shapes,this.rotate(diff)))
         },
-        crate::tree::WordTypes::Number(word) => {
-            let num = &Number::try_from(word.clone())?;
+        Number(num) => {
             let cart_pos:Cart = Cart::from(this.clone());
-            let mut these_shapes:BShape = (num,word_radius).into();
+            let mut these_shapes:BShape = (num,word_radius,opts).into();
             these_shapes.shove(cart_pos);
             Ok((these_shapes,this.rotate(diff)))
         },
-        Punctuation(word) => {
-            todo!()
+        Punctuation(marks) => {
+            let gap = match last {
+                Some(last) => this.divide(last).theta,
+                None => diff,//nothing before, so leave the usual gap
+            };
+            let ring = Polar::new(sen_rad, this.theta-gap/2.0);
+            let mark_radius = if word_radius > 0.0 {word_radius} else {get_word_rad(sen_rad,1,opts).0};//no words to size them from, so as if there were one
+            let these_shapes = draw_punctuation(marks, ring, mark_radius*0.15, mark_radius,opts)?;
+            Ok((these_shapes,*this))
         }
     }
 }
 
-impl TryFrom<(&Sentence,f64)> for BShape {//also return length
-    type Error = String;
-    fn try_from((sentence,sen_rad):(&Sentence,f64)) -> Result<Self,Self::Error> {
+impl TryFrom<(&Sentence,f64,&Alphabet,&RenderOptions)> for BShape {
+    type Error = GallifreyanError;
+    fn try_from((sentence,sen_rad,alphabet,opts):(&Sentence,f64,&Alphabet,&RenderOptions)) -> Result<Self,Self::Error> {
         let num_words = sentence.get_num_words();
 
-        let (word_radius,max_word_radius,diff) = get_word_rad(sen_rad,num_words);
+        let (word_radius,max_word_radius,diff) = get_word_rad(sen_rad,num_words,opts);
         let inner_radius = sen_rad-max_word_radius;
 
         let mut pos = Polar::new(inner_radius, -TAU/4.0);
-        let mut last = pos.clone();
+        let mut last:Option<Polar> = None;//the last word that took a place on the ring, punctuation doesn't
 
         let mut shapes:Shapes = Vec::new();
 
-        shapes.push(Box::new(Circle::new(Cart::origin(), sen_rad+2.0*Normal.val(sen_rad),Some(Normal.val(sen_rad)))));
-        shapes.push(Box::new(Circle::new(Cart::origin(), sen_rad+(Normal.val(sen_rad)/2.0),Some(Thin.val(sen_rad)))));
+        shapes.push(Box::new(Circle::new(Cart::origin(), sen_rad+2.0*Normal.val(sen_rad,opts),Some(Normal.val(sen_rad,opts)))));
+        shapes.push(Box::new(Circle::new(Cart::origin(), sen_rad+(Normal.val(sen_rad,opts)/2.0),Some(Thin.val(sen_rad,opts)))));
 
         for word in &sentence.words {
-            let (new_shapes,new_next) = draw_word(word, &pos, &last, diff,word_radius)?;
+            let (new_shapes,new_next) = draw_word(word, &pos, last.as_ref(), diff,(word_radius,sen_rad),alphabet,opts)?;
             shapes.push(new_shapes);
-            last = pos;
+            if !matches!(word, Punctuation(_)) {
+                last = Some(pos);
+            }
             pos = new_next;
         }
-        let shape = Box::new(ShapeSet::new(shapes, "sentence"));
+        let shape = Box::new(ShapeSet::new(shapes, "sentence").with_label(sentence.text(alphabet), "group"));
         return Ok(shape);
     }
 }
\ No newline at end of file
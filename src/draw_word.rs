@@ -1,14 +1,49 @@
 use std::f64::consts::{PI, TAU};
 use crate::shape::*;
+use crate::options::RenderOptions;
+use crate::alphabet::Alphabet;
 use crate::shape::Thickness::{Normal, Thick, Thin};
-use crate::tree::{Letter, Word, Consonant, Vowel, Marks, Vowels};
+use crate::tree::{Letter, Word, Consonant, Vowel, Marks};
 use crate::tree::Vowels::{A,E,I,O,U};
 use crate::tree::Arc::{Big,Small,Above,On};
 
-const VOWEL_MODIFIER:f64 = 0.1;
-const CONSONANT_MODIFIER:f64 = 0.6;
+const APOSTROPHE_GAP:f64 = 0.5;//how much of the arc between two letters an apostrophe leaves open
+const APOSTROPHE_TICK:f64 = 0.15;//length of the lines either side of the gap, in word radii
 
-fn one_letter_word(letter:&Letter,word_radius:f64) -> BShape {
+fn spaced(word:&Word) -> Vec<(&Letter,bool)> {//the letters that take up room, each with whether an apostrophe follows it
+    let mut result:Vec<(&Letter,bool)> = Vec::new();
+    let mut leading = false;
+    for l in word.word() {
+        match (l, result.last_mut()) {
+            (Letter::Apostrophe, Some((_,broken))) => *broken = true,
+            (Letter::Apostrophe, None) => leading = true,
+            _ => result.push((l,false)),
+        }
+    }
+    if leading && let Some((_,broken)) = result.last_mut() {//the arc after the last letter comes round to the first
+        *broken = true;
+    }
+    return result;
+}
+
+fn draw_connector(from:Polar, span:f64, broken:bool, word_radius:f64, opts:&RenderOptions) -> BShape {//the word circle from one letter to the next, span radians round
+    let width = Normal.val(word_radius,opts);
+    if !broken {
+        let to = from.rotate(span);
+        return Box::new(Arc::new(from.into(), to.into(), word_radius, span > PI, false, width));
+    }
+    let side = span*(1.0-APOSTROPHE_GAP)/2.0;
+    let (open,close) = (from.rotate(side), from.rotate(span-side));
+    let mut shapes = Shapes::new();
+    shapes.push(Box::new(Arc::new(from.into(), open.into(), word_radius, false, false, width)));
+    shapes.push(Box::new(Arc::new(close.into(), from.rotate(span).into(), word_radius, false, false, width)));
+    for edge in [open,close] {
+        shapes.push(Box::new(Line::new(edge.into(), edge.extend(word_radius*APOSTROPHE_TICK).into(), width, true)));
+    }
+    return Box::new(ShapeSet::new(shapes,"apostrophe").with_label("apostrophe".to_string(), "img"));
+}
+
+fn one_letter_word(word:&Word,(letter,broken):(&Letter,bool),word_radius:f64,alphabet:&Alphabet,opts:&RenderOptions) -> BShape {
     let pi = PI;
     let diff = pi/2.0;
     let start = Polar::new(word_radius, -(pi+diff)/2.0);
@@ -18,23 +53,23 @@ fn one_letter_word(letter:&Letter,word_radius:f64) -> BShape {
     let mut shapes = Shapes::new();
 
 
-    let mut letter = draw_letter(letter, (start,middle,end),word_radius);
+    let letter = draw_letter(letter, (start,middle,end),word_radius,alphabet,opts);
     shapes.push(letter);
 
-    let connector = Arc::new(end.into(), start.into(), word_radius, true, false, Normal.val(word_radius));
-    shapes.push(Box::new(connector));
-    return Box::new(ShapeSet::new(shapes,"plainword word"));
+    shapes.push(draw_connector(end, TAU-diff, broken, word_radius, opts));
+    return Box::new(ShapeSet::new(shapes,"plainword word").with_label(word.text(alphabet), "group"));
 
 }
 
-pub fn draw_plain_word(word: &Word, pos:&Polar,word_radius:f64) -> BShape {
+pub fn draw_plain_word(word: &Word, word_radius:f64,alphabet:&Alphabet,opts:&RenderOptions) -> BShape {//the alphabet spells the labels
     let num_parts = word.get_num_things();
+    let letters = spaced(word);
 
     if num_parts == 0 {
 
         return Box::new(ShapeSet::new(vec![],"plainword word"));
     } else if num_parts == 1 {
-        return one_letter_word(&word.word()[0],word_radius);
+        return one_letter_word(word,letters[0],word_radius,alphabet,opts);
     }
 
     let pi = std::f64::consts::PI;
@@ -43,42 +78,42 @@ pub fn draw_plain_word(word: &Word, pos:&Polar,word_radius:f64) -> BShape {
 
     let mut result = Shapes::new();
 
-    for l in word.word() {
+    for (l,broken) in letters {
         let middle = start.rotate(each/2.0);
         let end = middle.rotate(each/2.0);
 
-        result.push(draw_letter(l,(start, middle, end),word_radius));
+        result.push(draw_letter(l,(start, middle, end),word_radius,alphabet,opts));
 
-        let next = end.rotate(each);
-        let connecting_arc = Box::new(crate::shape::Arc::new(end.into(), next.into(), word_radius, false, false, Normal.val(word_radius)));
-        result.push(connecting_arc);
-        start = next;
+        result.push(draw_connector(end, each, broken, word_radius, opts));
+        start = end.rotate(each);
     }
-    return Box::new(ShapeSet::new(result,"plainword word"));
+    return Box::new(ShapeSet::new(result,"plainword word").with_label(word.text(alphabet), "group"));
 }
 
 
-fn draw_letter(letter:&Letter, (start,middle,end):(Polar,Polar,Polar),word_radius:f64) -> BShape {
+fn draw_letter(letter:&Letter, (start,middle,end):(Polar,Polar,Polar),word_radius:f64,alphabet:&Alphabet,opts:&RenderOptions) -> BShape {
     let std_dist = Cart::from(start).distance(&Cart::from(end));
-    return match letter {
-        Letter::VOpt(v) => draw_loose_vowel(v,(start,middle,end),std_dist,word_radius),
-        Letter::COpt(c) => draw_consonant(c,(start,middle,end),std_dist,word_radius)
-    }
+    let shapes = match letter {
+        Letter::VOpt(v) => draw_loose_vowel(v,(start,middle,end),std_dist,word_radius,opts),
+        Letter::COpt(c) => draw_consonant(c,(start,middle,end),std_dist,word_radius,opts),
+        Letter::Apostrophe => ShapeSet::new(vec![],"apostrophe"),//drawn by the connector before it instead
+    };
+    return Box::new(shapes.with_label(letter.text(alphabet).unwrap_or_default(), "img"));
 }
 
-fn draw_loose_vowel(vowel:&Vowel, (start,middle,end):(Polar,Polar,Polar),std_dist:f64,word_radius:f64) -> BShape {
+fn draw_loose_vowel(vowel:&Vowel, (start,middle,end):(Polar,Polar,Polar),std_dist:f64,word_radius:f64,opts:&RenderOptions) -> ShapeSet {
     let mut shapes = Shapes::new();
-    let connecting_arc = Box::new(crate::shape::Arc::new(start.into(), end.into(), word_radius, false, false, Normal.val(word_radius)));
+    let connecting_arc = Box::new(crate::shape::Arc::new(start.into(), end.into(), word_radius, false, false, Normal.val(word_radius,opts)));
     shapes.push(connecting_arc);
 
-    let inner = middle.extend(-std_dist*VOWEL_MODIFIER*1.01);
-    let outer = middle.extend(std_dist*VOWEL_MODIFIER*1.01);
-    shapes.push(draw_vowel(vowel,(inner,middle,outer),std_dist,word_radius));
+    let inner = middle.extend(-std_dist*opts.vowel_modifier*1.01);
+    let outer = middle.extend(std_dist*opts.vowel_modifier*1.01);
+    shapes.push(draw_vowel(vowel,(inner,middle,outer),std_dist,word_radius,opts));
 
-    return Box::new(ShapeSet::new(shapes,"letter"));
+    return ShapeSet::new(shapes,"letter");
 }
 
-fn draw_vowel(vowel:&Vowel, (inner,middle,outer):(Polar,Polar,Polar),std_dist:f64,word_radius:f64) -> BShape {
+fn draw_vowel(vowel:&Vowel, (inner,middle,outer):(Polar,Polar,Polar),std_dist:f64,word_radius:f64,opts:&RenderOptions) -> BShape {
     let mut shapes = Shapes::new();
 
     let polar_centre = match vowel.v {
@@ -87,62 +122,62 @@ fn draw_vowel(vowel:&Vowel, (inner,middle,outer):(Polar,Polar,Polar),std_dist:f6
         O => inner,
     };
     let cart_centre:Cart = polar_centre.into();
-    let radius = std_dist * VOWEL_MODIFIER;
-    let circle = Circle::new(cart_centre, radius, Some(Normal.val(word_radius)));
+    let radius = std_dist * opts.vowel_modifier;
+    let circle = Circle::new(cart_centre, radius, Some(Normal.val(word_radius,opts)));
     shapes.push(Box::new(circle));
     if vowel.double {
-        let other_circle = Circle::new(cart_centre, radius/2.0, Some(Thin.val(word_radius)));
+        let other_circle = Circle::new(cart_centre, radius/2.0, Some(Thin.val(word_radius,opts)));
         shapes.push(Box::new(other_circle));
     }
     match vowel.v {
         I => {
             let start:Cart = polar_centre.extend(-radius).into();
             let end = Cart::origin();
-            let line = Line::new(start,end,Normal.val(word_radius),true);
+            let line = Line::new(start,end,Normal.val(word_radius,opts),true);
             shapes.push(Box::new(line));
         },
         U => {
             let start:Cart = polar_centre.extend(radius).into();
             let end:Cart = Polar::new(word_radius*1.3,polar_centre.theta).into();
-            let line = Line::new(start,end,Normal.val(word_radius),true);
+            let line = Line::new(start,end,Normal.val(word_radius,opts),true);
             shapes.push(Box::new(line));
         },
         _ => ()
     }
     return Box::new(ShapeSet::new(shapes,"vowel"));
 }
-fn draw_consonant(consonant: &Consonant, (start,middle,end):(Polar,Polar,Polar),std_dist:f64,word_radius:f64) -> BShape {
+fn draw_consonant(consonant: &Consonant, (start,middle,end):(Polar,Polar,Polar),std_dist:f64,word_radius:f64,opts:&RenderOptions) -> ShapeSet {
     let mut shapes = Shapes::new();
     match consonant.arc {
-        Above|On => shapes.push(Box::new(crate::shape::Arc::new(start.into(), end.into(), word_radius, false, false, Normal.val(word_radius)))),
+        Above|On => shapes.push(Box::new(crate::shape::Arc::new(start.into(), end.into(), word_radius, false, false, Normal.val(word_radius,opts)))),
         _ => ()
     }
 
     let mut new_shapes:Shapes= match consonant.arc {
-        Big => get_big_arc((start,middle,end),&consonant.marks,&consonant.diacritic,word_radius),
-        Above => get_above_arc(middle,std_dist,&consonant.marks,&consonant.diacritic,word_radius),
-        Small =>  get_small_arc((start,middle,end),std_dist,&consonant.marks,&consonant.diacritic,word_radius),
-        On => get_on_arc(middle,std_dist,&consonant.marks,&consonant.diacritic,word_radius)
+        Big => get_big_arc((start,middle,end),&consonant.marks,&consonant.diacritic,word_radius,opts),
+        Above => get_above_arc(middle,std_dist,&consonant.marks,&consonant.diacritic,word_radius,opts),
+        Small =>  get_small_arc((start,middle,end),std_dist,&consonant.marks,&consonant.diacritic,word_radius,opts),
+        On => get_on_arc(middle,std_dist,&consonant.marks,&consonant.diacritic,word_radius,opts)
     };
     shapes.append(&mut new_shapes);
 
-    return Box::new(ShapeSet::new(shapes,"letter consonant"));
+    return ShapeSet::new(shapes,"letter consonant");
 }
 
 
-fn get_big_arc((start,middle,end):(Polar,Polar,Polar),marks: &Option<Marks>,diacritic:&Option<Vowel>,word_radius:f64) -> Shapes {
+fn get_big_arc((start,middle,end):(Polar,Polar,Polar),marks: &Option<Marks>,diacritic:&Option<Vowel>,word_radius:f64,opts:&RenderOptions) -> Shapes {
     let diff = end.divide(&start).theta/4.0;
     let in_start:Cart = start.rotate(diff).into();
     let in_end:Cart = end.rotate(-diff).into();
     let std_dist = in_start.distance(&in_end);
-    let radius = std_dist * CONSONANT_MODIFIER;
+    let radius = std_dist * opts.consonant_modifier;
 
     let mut shapes = Shapes::new();
-    shapes.push(Box::new(Arc::new(in_start, in_end, radius, true, true, Normal.val(word_radius))));
-    shapes.push(Box::new(Circle::new(in_start,Normal.val(word_radius)*0.5,None)));
-    shapes.push(Box::new(Circle::new(in_end,Normal.val(word_radius)*0.5,None)));
-    let start_arc = Arc::new(start.into(),in_start.into(),middle.radius,false,false,Normal.val(word_radius));
-    let end_arc = Arc::new(in_end.into(),end.into(),middle.radius,false,false,Normal.val(word_radius));
+    shapes.push(Box::new(Arc::new(in_start, in_end, radius, true, true, Normal.val(word_radius,opts))));
+    shapes.push(Box::new(Circle::new(in_start,Normal.val(word_radius,opts)*0.5,None)));
+    shapes.push(Box::new(Circle::new(in_end,Normal.val(word_radius,opts)*0.5,None)));
+    let start_arc = Arc::new(start.into(),in_start.into(),middle.radius,false,false,Normal.val(word_radius,opts));
+    let end_arc = Arc::new(in_end.into(),end.into(),middle.radius,false,false,Normal.val(word_radius,opts));
     shapes.push(Box::new(start_arc));
     shapes.push(Box::new(end_arc));
 
@@ -154,15 +189,15 @@ fn get_big_arc((start,middle,end):(Polar,Polar,Polar),marks: &Option<Marks>,diac
     if let Some(v) = diacritic {
         let c_start:Cart = start.into();
         let v_std_dist = c_start.distance(&end.into());
-        let v_pos = get_big_arc_v_pos(middle,radius,v_std_dist,centre);
-        shapes.push(draw_vowel(&v,v_pos,v_std_dist,word_radius));
+        let v_pos = get_big_arc_v_pos(middle,radius,v_std_dist,centre,opts);
+        shapes.push(draw_vowel(&v,v_pos,v_std_dist,word_radius,opts));
         avoid_centre = v.v.centre()
     }else {
         avoid_centre = false;
     }
     if let Some(m) = marks {
         let relative_pos = recenter_mark_pos(centre, in_start, in_end);
-        shapes.append(&mut add_marks(m,centre.into(),relative_pos,avoid_centre,std_dist,word_radius))
+        shapes.append(&mut add_marks(m,centre.into(),relative_pos,avoid_centre,std_dist,word_radius,opts))
     }
     return shapes;
 }
@@ -174,8 +209,8 @@ fn recenter_mark_pos(centre:Polar, start:Cart, end:Cart) -> (Polar, Polar) {
     return (start.into(),end.into());
 }
 
-fn get_big_arc_v_pos(middle:Polar, inner_radius:f64,v_std_dist:f64,centre:Polar) -> (Polar,Polar,Polar) {
-    let outer = middle.extend(v_std_dist*VOWEL_MODIFIER*1.1);
+fn get_big_arc_v_pos(middle:Polar, inner_radius:f64,v_std_dist:f64,centre:Polar,opts:&RenderOptions) -> (Polar,Polar,Polar) {
+    let outer = middle.extend(v_std_dist*opts.vowel_modifier*1.1);
     let middle = centre;
     let inner = centre.extend(-inner_radius);
     return (inner,middle,outer);
@@ -193,18 +228,18 @@ fn get_centre_radius(r1:f64,r2:f64,alpha:f64,inner:bool) -> f64 {
     let result =  r2 * ep.sin()/shal;
     return result;
 }
-fn get_above_arc(middle:Polar,std_dist:f64,marks: &Option<Marks>,diacritic:&Option<Vowel>,word_radius:f64) -> Shapes {
-    let radius = std_dist * CONSONANT_MODIFIER*0.5;
+fn get_above_arc(middle:Polar,std_dist:f64,marks: &Option<Marks>,diacritic:&Option<Vowel>,word_radius:f64,opts:&RenderOptions) -> Shapes {
+    let radius = std_dist * opts.consonant_modifier*0.5;
     let centre = middle.extend(-radius*1.1).into();
-    let mut shapes:Shapes =  vec![Box::new(Circle::new(centre,radius, Some(Normal.val(word_radius))))];
+    let mut shapes:Shapes =  vec![Box::new(Circle::new(centre,radius, Some(Normal.val(word_radius,opts))))];
 
     let avoid_centre:bool;
     if let Some(v) = diacritic {
-        let outer = middle.extend(std_dist*VOWEL_MODIFIER*1.1);
+        let outer = middle.extend(std_dist*opts.vowel_modifier*1.1);
         let middle = middle.extend(-radius*1.1);
         let inner = middle.extend(-radius);
         let v_pos = (inner,middle,outer);
-        shapes.push(draw_vowel(&v,v_pos,std_dist,word_radius));
+        shapes.push(draw_vowel(&v,v_pos,std_dist,word_radius,opts));
         avoid_centre = v.v.centre()
     }else {
         avoid_centre = false;
@@ -212,45 +247,45 @@ fn get_above_arc(middle:Polar,std_dist:f64,marks: &Option<Marks>,diacritic:&Opti
     if let Some(m) = marks {
         let start = Polar::new(radius,middle.theta);
         let relative_pos = (start.rotate(7f64*TAU/8f64),start.rotate(TAU/8f64));
-        shapes.append(&mut add_marks(m,centre,relative_pos,avoid_centre,std_dist*0.75,word_radius));
+        shapes.append(&mut add_marks(m,centre,relative_pos,avoid_centre,std_dist*0.75,word_radius,opts));
     }
     return shapes;
 }
-fn get_small_arc((start,middle,end):(Polar,Polar,Polar),std_dist:f64,marks: &Option<Marks>,diacritic:&Option<Vowel>,word_radius:f64) -> Shapes {
-    let radius = std_dist * CONSONANT_MODIFIER;
-    let mut shapes:Shapes = vec![Box::new(Arc::new(start.into(),end.into(),radius,false,true,Normal.val(word_radius)))];
-    shapes.push(Box::new(Circle::new(start.into(),Normal.val(word_radius)*0.5,None)));
-    shapes.push(Box::new(Circle::new(end.into(),Normal.val(word_radius)*0.5,None)));
+fn get_small_arc((start,middle,end):(Polar,Polar,Polar),std_dist:f64,marks: &Option<Marks>,diacritic:&Option<Vowel>,word_radius:f64,opts:&RenderOptions) -> Shapes {
+    let radius = std_dist * opts.consonant_modifier;
+    let mut shapes:Shapes = vec![Box::new(Arc::new(start.into(),end.into(),radius,false,true,Normal.val(word_radius,opts)))];
+    shapes.push(Box::new(Circle::new(start.into(),Normal.val(word_radius,opts)*0.5,None)));
+    shapes.push(Box::new(Circle::new(end.into(),Normal.val(word_radius,opts)*0.5,None)));
 
-    let outer = middle.extend(std_dist*VOWEL_MODIFIER*1.1);
+    let outer = middle.extend(std_dist*opts.vowel_modifier*1.1);
     let centre_radius = get_centre_radius(word_radius,radius,end.divide(&start).theta,false);
     let centre = Polar::new(centre_radius,middle.theta);
     let avoid_centre:bool;
     if let Some(v) = diacritic {
         let inner = centre.extend(-radius);
         let middle = Polar::new((inner.radius+word_radius)/2.0,middle.theta);
-        shapes.push(draw_vowel(v,(inner,middle,outer),std_dist,word_radius));
+        shapes.push(draw_vowel(v,(inner,middle,outer),std_dist,word_radius,opts));
         avoid_centre = v.v.centre();
     } else {
         avoid_centre = false;
     }
     if let Some(m) = marks {
         let relative_pos = recenter_mark_pos(centre, start.into(), end.into());
-        shapes.append(&mut add_marks(m,centre.into(),relative_pos,avoid_centre,std_dist,word_radius))
+        shapes.append(&mut add_marks(m,centre.into(),relative_pos,avoid_centre,std_dist,word_radius,opts))
     }
     return shapes;
 }
 
-fn get_on_arc(middle:Polar,std_dist:f64,marks: &Option<Marks>,diacritic:&Option<Vowel>,word_radius:f64) -> Shapes {
-    let radius = std_dist * CONSONANT_MODIFIER*0.5;
-    let mut shapes:Shapes = vec![Box::new(Circle::new(middle.into(),radius, Some(Normal.val(word_radius))))];
+fn get_on_arc(middle:Polar,std_dist:f64,marks: &Option<Marks>,diacritic:&Option<Vowel>,word_radius:f64,opts:&RenderOptions) -> Shapes {
+    let radius = std_dist * opts.consonant_modifier*0.5;
+    let mut shapes:Shapes = vec![Box::new(Circle::new(middle.into(),radius, Some(Normal.val(word_radius,opts))))];
 
     let avoid_centre:bool;
     if let Some(v) = diacritic {
         let middle = middle;
         let inner = middle.extend(-radius);
         let outer = middle.extend(radius/2.0);
-        shapes.push(draw_vowel(v,(inner,middle,outer),std_dist,word_radius));
+        shapes.push(draw_vowel(v,(inner,middle,outer),std_dist,word_radius,opts));
         avoid_centre = v.v.centre();
     } else {
         avoid_centre = false;
@@ -260,12 +295,12 @@ fn get_on_arc(middle:Polar,std_dist:f64,marks: &Option<Marks>,diacritic:&Option<
         let r2 = radius;
         let diff:f64 = (1f64- r2*r2/(2.0*r1*r1)).acos();
         let relative_pos = recenter_mark_pos(middle, middle.rotate(-diff).into(), middle.rotate(diff).into());
-        shapes.append(&mut add_marks(m,middle.into(),relative_pos,avoid_centre,std_dist,word_radius))
+        shapes.append(&mut add_marks(m,middle.into(),relative_pos,avoid_centre,std_dist,word_radius,opts))
     }
     return shapes;
 }
 const SHOW_ENDS:bool = false;
-fn add_marks(marks:&Marks,centre:Cart,(start,end):(Polar,Polar),avoid_centre:bool,std_dist:f64,word_radius:f64) -> Shapes {//centre co-ord is wrt the word's centre, start and end are wrt centre
+fn add_marks(marks:&Marks,centre:Cart,(start,end):(Polar,Polar),avoid_centre:bool,std_dist:f64,word_radius:f64,opts:&RenderOptions) -> Shapes {//centre co-ord is wrt the word's centre, start and end are wrt centre
     let (num,is_line):(i32,bool) = match marks {
         Marks::Line(n) => (*n,true),
         Marks::Dot(n) => (*n,false),
@@ -287,11 +322,11 @@ fn add_marks(marks:&Marks,centre:Cart,(start,end):(Polar,Polar),avoid_centre:boo
         cpos = ppos.into();
         cpos.shove(centre);
         shapes.push(if is_line {
-            let mut end :Cart= ppos.extend(std_dist*CONSONANT_MODIFIER).into();
+            let mut end :Cart= ppos.extend(std_dist*opts.consonant_modifier).into();
             end.shove(centre);
-            Box::new(Line::new(cpos,end,Thick.val(word_radius),true))
+            Box::new(Line::new(cpos,end,Thick.val(word_radius,opts),true))
         } else {
-            Box::new(Circle::new(cpos,std_dist*CONSONANT_MODIFIER*0.1,Some(Thin.val(word_radius))))
+            Box::new(Circle::new(cpos,std_dist*opts.consonant_modifier*0.1,Some(Thin.val(word_radius,opts))))
         });
     }
     for _ in 0..num as i32 {
@@ -299,11 +334,11 @@ fn add_marks(marks:&Marks,centre:Cart,(start,end):(Polar,Polar),avoid_centre:boo
         cpos = ppos.into();
         cpos.shove(centre);
         shapes.push(if is_line {
-            let mut end :Cart= ppos.extend(std_dist*CONSONANT_MODIFIER*0.6).into();
+            let mut end :Cart= ppos.extend(std_dist*opts.consonant_modifier*0.6).into();
             end.shove(centre);
-            Box::new(Line::new(cpos,end,Thick.val(word_radius),true))
+            Box::new(Line::new(cpos,end,Thick.val(word_radius,opts),true))
         } else {
-            Box::new(Circle::new(cpos,std_dist*CONSONANT_MODIFIER*0.1,None))
+            Box::new(Circle::new(cpos,std_dist*opts.consonant_modifier*0.1,None))
         });
     }
     if SHOW_ENDS {
@@ -311,11 +346,11 @@ fn add_marks(marks:&Marks,centre:Cart,(start,end):(Polar,Polar),avoid_centre:boo
         cpos = ppos.into();
         cpos.shove(centre);
         shapes.push(if is_line {
-            let mut end: Cart = ppos.extend(std_dist * CONSONANT_MODIFIER).into();
+            let mut end: Cart = ppos.extend(std_dist * opts.consonant_modifier).into();
             end.shove(centre);
-            Box::new(Line::new(cpos, end, Thick.val(word_radius),true))
+            Box::new(Line::new(cpos, end, Thick.val(word_radius,opts),true))
         } else {
-            Box::new(Circle::new(cpos, std_dist * CONSONANT_MODIFIER*0.1,Some(Thick.val(word_radius))))
+            Box::new(Circle::new(cpos, std_dist * opts.consonant_modifier*0.1,Some(Thick.val(word_radius,opts))))
         });
     }
     return shapes;
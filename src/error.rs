@@ -0,0 +1,115 @@
+use std::fmt;
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Default)]
+pub struct Offset {//where in the text something went wrong, counted in both bytes and chars
+    pub byte: usize,
+    pub char: usize,
+}
+impl Offset {
+    pub fn new(byte:usize, char:usize) -> Self {
+        Self { byte, char }
+    }
+    pub fn at(chars:&[char], index:usize) -> Self {
+        let byte = chars[..index].iter().map(|c| c.len_utf8()).sum();
+        Self { byte, char:index }
+    }
+    pub fn shift(&self, by:Offset) -> Self {
+        Self::new(self.byte+by.byte, self.char+by.char)
+    }
+}
+
+use crate::error::GallifreyanError::*;
+#[derive(Debug,Clone,PartialEq)]
+pub enum GallifreyanError {
+    InvalidCharacter { character:char, offset:Offset },//can't appear anywhere in the text
+    InvalidLetter { character:char, offset:Offset },
+    InvalidDigit { character:char, offset:Offset },
+    InvalidPunctuation { character:char, offset:Offset },
+    EmptyNumber { offset:Offset },
+    InvalidNumber { reason:String, offset:Offset },
+    InvalidPhoneme { phoneme:String, offset:Offset },
+    InvalidSvg { reason:String, offset:Offset },//the offset is into the svg being decoded
+    InvalidAlphabet { reason:String },//a letter table that can't be used, found when it is loaded
+}
+
+impl GallifreyanError {
+    pub fn character(&self) -> Option<char> {
+        match self {
+            InvalidCharacter{character, ..}|InvalidLetter{character, ..}|InvalidDigit{character, ..}|InvalidPunctuation{character, ..} => Some(*character),
+            EmptyNumber{..}|InvalidNumber{..}|InvalidPhoneme{..}|InvalidSvg{..}|InvalidAlphabet{..} => None,
+        }
+    }
+    pub fn offset(&self) -> Offset {
+        match self {
+            InvalidCharacter{offset, ..}|InvalidLetter{offset, ..}|InvalidDigit{offset, ..}|InvalidPunctuation{offset, ..}|EmptyNumber{offset}|InvalidNumber{offset, ..}|InvalidPhoneme{offset, ..}|InvalidSvg{offset, ..} => *offset,
+            InvalidAlphabet{..} => Offset::default(),
+        }
+    }
+    fn with_offset(mut self, new:Offset) -> Self {
+        match &mut self {
+            InvalidCharacter{offset, ..}|InvalidLetter{offset, ..}|InvalidDigit{offset, ..}|InvalidPunctuation{offset, ..}|EmptyNumber{offset}|InvalidNumber{offset, ..}|InvalidPhoneme{offset, ..}|InvalidSvg{offset, ..} => *offset = new,
+            InvalidAlphabet{..} => (),
+        }
+        return self;
+    }
+    pub(crate) fn shift(self, by:Offset) -> Self {//offsets start relative to the piece being parsed, move them out to the whole text
+        let offset = self.offset().shift(by);
+        self.with_offset(offset)
+    }
+    pub(crate) fn locate(self, origins:&[Offset]) -> Self {//map a position in the cleaned up chars back to the original input
+        match origins.get(self.offset().char) {
+            Some(offset) => {
+                let offset = *offset;
+                self.with_offset(offset)
+            },
+            None => self
+        }
+    }
+}
+
+impl fmt::Display for GallifreyanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidCharacter{character, offset} => write!(f, "{:#?} at position {} is not a valid letter", character, offset.char),
+            InvalidLetter{character, offset} => write!(f, "Invalid letter found: {:#?} at position {}", character, offset.char),
+            InvalidDigit{character, offset} => write!(f, "invalid digit {:#?} at position {}", character, offset.char),
+            InvalidPunctuation{character, offset} => write!(f, "{:#?} at position {} is not valid punctuation", character, offset.char),
+            EmptyNumber{offset} => write!(f, "empty number at position {}", offset.char),
+            InvalidNumber{reason, offset} => write!(f, "invalid number at position {}: {}", offset.char, reason),
+            InvalidPhoneme{phoneme, offset} => write!(f, "{:#?} at position {} is not a phoneme Sherman has a letter for", phoneme, offset.char),
+            InvalidSvg{reason, offset} => write!(f, "could not decode the svg at position {}: {}", offset.char, reason),
+            InvalidAlphabet{reason} => write!(f, "invalid alphabet: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for GallifreyanError {}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Warning {//a char that was skipped or replaced so the rest of the text could still be drawn
+    pub character: char,
+    pub offset: Offset,
+    pub replacement: Option<char>,//none if it was skipped
+}
+
+impl Warning {
+    pub(crate) fn shift(mut self, by:Offset) -> Self {
+        self.offset = self.offset.shift(by);
+        return self;
+    }
+    pub(crate) fn locate(mut self, origins:&[Offset]) -> Self {
+        if let Some(offset) = origins.get(self.offset.char) {
+            self.offset = *offset;
+        }
+        return self;
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.replacement {
+            Some(r) => write!(f, "{:#?} at position {} is not a valid letter, so it was replaced with {:#?}", self.character, self.offset.char, r),
+            None => write!(f, "{:#?} at position {} is not a valid letter, so it was skipped", self.character, self.offset.char),
+        }
+    }
+}
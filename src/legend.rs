@@ -0,0 +1,98 @@
+use std::fmt::Write;
+use crate::draw_word::draw_plain_word;
+use crate::shape::{BShape, Cart};
+use crate::svg::{escape, SvgWriter};
+use crate::alphabet::Alphabet;
+use crate::tree::{Digit, Letter, Number, Word};
+use crate::options::RenderOptions;
+
+//a chart of every letter and digit, drawn by the same code as everything else so it can't go out of date
+
+const COLUMNS:usize = 8;
+const CELL:f64 = 100.0;//each glyph gets a square this wide
+const WORD_RADIUS:f64 = 26.0;
+const LABEL:f64 = 22.0;//room under the square for the glyph's name
+const HEADING:f64 = 34.0;
+
+enum Entry {
+    Letter(Letter),
+    Digit(u8),
+}
+
+fn entries(spellings:&[(String,&Letter)], double:bool, vowels:bool) -> Vec<(String,Entry)> {
+    spellings.iter()
+        .filter(|(s,l)| (s.len() == 2) == double && matches!(l, Letter::VOpt(_)) == vowels)
+        .map(|(s,l)| (s.clone(), Entry::Letter((*l).clone())))
+        .collect()
+}
+
+fn sections(alphabet:&Alphabet) -> Vec<(&'static str, Vec<(String,Entry)>)> {
+    let spellings = alphabet.spellings();
+    return vec![
+        ("Vowels", entries(&spellings, false, true)),
+        ("Consonants", entries(&spellings, false, false)),
+        ("Digraphs", entries(&spellings, true, false)),
+        ("Double vowels", entries(&spellings, true, true)),
+        ("Digits", (0..10).map(|d| (d.to_string(), Entry::Digit(d))).collect()),
+    ];
+}
+
+fn draw_entry(entry:&Entry, alphabet:&Alphabet, opts:&RenderOptions) -> BShape {
+    match entry {
+        Entry::Letter(letter) => draw_plain_word(&Word::from(vec![letter.clone()]), WORD_RADIUS, alphabet, opts),
+        Entry::Digit(d) => {
+            let digit = Digit{num_circles: d/5, num_lines: d%5, follows_dot: false, follows_comma: false};
+            let number = Number{is_positive: true, is_whole: true, base: 10, digits: vec![digit], suffix: None};
+            (&number, WORD_RADIUS, opts).into()
+        },
+    }
+}
+
+pub fn render_legend(alphabet:&Alphabet, opts:&RenderOptions) -> String {
+    let mut body = String::new();
+    let mut y = 0.0;
+    for (heading,entries) in sections(alphabet) {
+        let _ = writeln!(body, "<text class=\"legend_heading\" x=\"{}\" y=\"{}\" font-size=\"20\">{}</text>", CELL*0.1, y+HEADING*0.7, heading);
+        y += HEADING;
+        for (i,(name,entry)) in entries.iter().enumerate() {
+            let corner = Cart::new((i%COLUMNS) as f64*CELL, y+(i/COLUMNS) as f64*(CELL+LABEL));
+            let mut shape = draw_entry(entry, alphabet, opts);
+            shape.shove(Cart::new(corner.x+CELL/2.0, corner.y+CELL/2.0));
+            let mut writer = SvgWriter::new(opts);
+            shape.draw(&mut writer);
+            let _ = writeln!(body, "<g class=\"legend_entry\">{}\n<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"16\">{}</text></g>",
+                             writer.finish(), corner.x+CELL/2.0, corner.y+CELL+LABEL*0.5, escape(name));
+        }
+        y += entries.len().div_ceil(COLUMNS) as f64*(CELL+LABEL);
+    }
+    let width = COLUMNS as f64*CELL;
+    return format!("<svg
+  viewBox=\"0 0 {width} {y}\"
+  version=\"1.1\"
+  xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\"
+  id=\"generated_legend\">
+    <g id=\"all_gall\" font-family=\"sans-serif\">
+{body}    </g>
+</svg>");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn every_letter_is_charted() {
+        let svg = render_legend(Alphabet::sherman(), &RenderOptions::default());
+        let root = xml::parse(&svg).unwrap();
+        let chart = root.find("g").unwrap();
+        let names:Vec<&str> = chart.elements()
+            .filter(|e| e.has_class("legend_entry"))
+            .map(|e| e.find("text").unwrap().text.as_str())
+            .collect();
+        assert_eq!(names.len(), Alphabet::sherman().spellings().len()+10);
+        for name in ["a", "b", "th", "qu", "ee", "7"] {
+            assert!(names.contains(&name), "{:#?} is missing from the legend", name);
+        }
+    }
+}
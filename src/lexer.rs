@@ -0,0 +1,279 @@
+use crate::error::{GallifreyanError, Offset, Warning};
+use crate::options::{ParseOptions, Unknown};
+use crate::tree::PunctuationTypes::{self, NEnd, SEnd};
+
+//splits the cleaned up text into tokens, before any of them are read as letters or numbers
+
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub(crate) enum TokenKind {
+    Letters,//a to z, with any apostrophes between them
+    Digits,//a number, with its sign and decimal points
+    Marks,//punctuation, only spaces between marks don't split them up
+    Break,//a blank line, which starts a new paragraph
+}
+use TokenKind::*;
+
+#[derive(Debug,Clone,PartialEq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub chars: Vec<char>,
+    pub start: usize,//char index of the first char in the input
+}
+
+#[derive(Debug,Copy,Clone,PartialEq)]
+enum Class {//what a char can be, before looking at what is around it
+    Letter,
+    Digit,
+    Space,
+    Mark,
+}
+
+fn class(c:char) -> Option<Class> {
+    match c {
+        'a'..='z' => Some(Class::Letter),
+        '0'..='9' => Some(Class::Digit),
+        ' '|'\t'|'\n'|'\r' => Some(Class::Space),
+        _ if is_mark(c) => Some(Class::Mark),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_mark(c:char) -> bool {
+    ".,;:?!\"'-".contains(c)
+}
+
+pub(crate) fn to_mark(c:char) -> PunctuationTypes {
+    if ".?!".contains(c) {SEnd(c)} else {NEnd(c)}
+}
+
+fn read_char(c:char, offset:Offset, options:&ParseOptions, warnings:&mut Vec<Warning>) -> Result<Option<char>,GallifreyanError> {//none if it should be skipped
+    if class(c).is_some() {
+        return Ok(Some(c));
+    }
+    match options.unknown {
+        Unknown::Error => Err(GallifreyanError::InvalidCharacter{character:c, offset}),
+        Unknown::Replace if class(options.replacement).is_some() => {
+            warnings.push(Warning{character:c, offset, replacement:Some(options.replacement)});
+            Ok(Some(options.replacement))
+        },
+        _ => {
+            warnings.push(Warning{character:c, offset, replacement:None});
+            Ok(None)
+        },
+    }
+}
+
+fn starts_number(input:&[char], i:usize) -> bool {//a digit, or a decimal point with one after it
+    match input.get(i) {
+        Some('.') => input.get(i+1).is_some_and(|c| c.is_ascii_digit()),
+        Some(c) => c.is_ascii_digit(),
+        None => false,
+    }
+}
+
+fn signed_number_follows(input:&[char], i:usize) -> bool {//a number, with or without a minus sign, so what is under a fraction is read in even when it isn't allowed
+    starts_number(input, i) || (input.get(i) == Some(&'-') && starts_number(input, i+1))
+}
+
+fn dots_touch_number(input:&[char], i:usize) -> bool {//a run of two or more dots starting here, with a digit right before or after it
+    if i > 0 && input[i-1] == '.' {
+        return false;
+    }
+    let end = i + input[i..].iter().take_while(|c| **c == '.').count();
+    let digit = |c:Option<&char>| c.is_some_and(|c| c.is_ascii_digit());
+    return end-i > 1 && (digit(i.checked_sub(1).and_then(|j| input.get(j))) || digit(input.get(end)));
+}
+
+fn digit_follows(input:&[char], i:usize, prefix:char) -> bool {//one that the base the prefix stands for has
+    let base = match prefix {
+        'b' => 2,
+        'o' => 8,
+        _ => 16,
+    };
+    input.get(i).is_some_and(|c| c.is_digit(base))
+}
+
+fn exponent_follows(input:&[char], i:usize) -> bool {//the digits of a power of ten, with or without a minus sign
+    let digit = |i:usize| input.get(i).is_some_and(|c| c.is_ascii_digit());
+    digit(i) || (input.get(i) == Some(&'-') && digit(i+1))
+}
+
+fn thousands_follow(input:&[char], i:usize) -> bool {//exactly three digits, so a list like 1,2 is still split at the comma
+    let digit = |i:usize| input.get(i).is_some_and(|c| c.is_ascii_digit());
+    (i..i+3).all(digit) && !digit(i+3)
+}
+
+pub(crate) fn lex(input:&[char], options:&ParseOptions, warnings:&mut Vec<Warning>) -> Result<Vec<Token>,GallifreyanError> {
+    let mut tokens:Vec<Token> = Vec::new();
+    let mut open = false;//whether the last token can still grow, a space closes letters and digits but not marks
+    let mut newlines = 0;//in the whitespace since the last token
+    for i in 0..input.len() {
+        let in_number = open && tokens.last().is_some_and(|t| t.kind == Digits);
+        let c = match input[i] {
+            '%' if in_number => '%',
+            '/' if in_number && signed_number_follows(input, i+1) => '/',
+            c => match read_char(c, Offset::at(input,i), options, warnings)? {
+                Some(c) => c,
+                None => continue,
+            },
+        };
+        if c == '.' && dots_touch_number(input, i) {//it can't be read as one decimal point, and shouldn't be quietly read as a full stop
+            return Err(GallifreyanError::InvalidNumber{reason:"it has more than one decimal point in a row".to_string(), offset:Offset::at(input,i+1)});
+        }
+        let next = input.get(i+1).and_then(|n| class(*n));
+        let last = if open {tokens.last().map(|t| t.kind)} else {None};
+        let (kind,joins) = match c {
+            _ if class(c) == Some(Class::Space) => {
+                newlines += if c == '\n' {1} else {0};
+                if newlines == 2 && !tokens.is_empty() {
+                    tokens.push(Token{kind:Break, chars:Vec::new(), start:i});
+                }
+                open = last == Some(Marks) && newlines < 2;
+                continue;
+            },
+            '.' if last == Some(Digits) && tokens.last().is_some_and(|t| t.chars.contains(&'x')) && digit_follows(input, i+1, 'x') => (Digits,true),//a hexadecimal point, as in 0xa.8
+            'a'..='f' if last == Some(Digits) && tokens.last().is_some_and(|t| t.chars.contains(&'x')) => (Digits,true),//hexadecimal digits
+            'b'|'o'|'x' if last == Some(Digits) && tokens.last().is_some_and(|t| matches!(t.chars[..], ['0']|['-','0'])) && digit_follows(input, i+1, c) => (Digits,true),//a base prefix, as in 0x1f
+            'e' if last == Some(Digits) && exponent_follows(input, i+1) => (Digits,true),//scientific notation, as in 6.02e23
+            '-' if last == Some(Digits) && tokens.last().is_some_and(|t| matches!(t.chars.last(), Some('e'|'/'))) => (Digits,true),
+            ',' if last == Some(Digits) && thousands_follow(input, i+1) => (Digits,true),//a thousands separator, as in 1,000
+            '/'|'%' => (Digits,true),//only read in when they follow a number
+            'a'..='z' => (Letters,true),
+            '0'..='9' => (Digits,true),
+            '.' if last == Some(Digits) && next == Some(Class::Digit) => (Digits,true),//a decimal point
+            '.' if last != Some(Letters) && next == Some(Class::Digit) => (Digits,false),//a number starting with its decimal point, as in .5
+            '-' if starts_number(input, i+1) => (Digits,false),//a minus sign always starts a new number
+            '\'' if last == Some(Letters) && next == Some(Class::Letter) => (Letters,true),
+            _ => (Marks,true),
+        };
+        match tokens.last_mut() {
+            Some(token) if joins && last == Some(kind) => token.chars.push(c),
+            _ => tokens.push(Token{kind, chars:vec![c], start:i}),
+        }
+        open = true;
+        newlines = 0;
+    }
+    return Ok(tokens);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(text:&str) -> Vec<(TokenKind,String)> {
+        let chars:Vec<char> = text.chars().collect();
+        lex(&chars, &ParseOptions::default(), &mut Vec::new()).unwrap().into_iter()
+            .map(|t| (t.kind, t.chars.into_iter().collect()))
+            .collect()
+    }
+
+    fn expect(kinds:&[(TokenKind,&str)]) -> Vec<(TokenKind,String)> {
+        kinds.iter().map(|(k,s)| (*k, s.to_string())).collect()
+    }
+
+    #[test]
+    fn kinds() {
+        let cases:&[(&str,&[(TokenKind,&str)])] = &[
+            ("hello", &[(Letters,"hello")]),
+            ("hello world", &[(Letters,"hello"), (Letters,"world")]),
+            ("\"hi,\" she said", &[(Marks,"\""), (Letters,"hi"), (Marks,",\""), (Letters,"she"), (Letters,"said")]),
+            ("wait: go; now", &[(Letters,"wait"), (Marks,":"), (Letters,"go"), (Marks,";"), (Letters,"now")]),
+            ("what?! no.", &[(Letters,"what"), (Marks,"?!"), (Letters,"no"), (Marks,".")]),
+            ("hi . , there", &[(Letters,"hi"), (Marks,".,"), (Letters,"there")]),
+            ("don't", &[(Letters,"don't")]),
+            ("cats' toys", &[(Letters,"cats"), (Marks,"'"), (Letters,"toys")]),
+            ("'tis", &[(Marks,"'"), (Letters,"tis")]),
+            ("well-known", &[(Letters,"well"), (Marks,"-"), (Letters,"known")]),
+            ("12 -3.5", &[(Digits,"12"), (Digits,"-3.5")]),
+            ("5-3", &[(Digits,"5"), (Digits,"-3")]),
+            ("abc123def", &[(Letters,"abc"), (Digits,"123"), (Letters,"def")]),
+            ("42.", &[(Digits,"42"), (Marks,".")]),
+            ("1 2", &[(Digits,"1"), (Digits,"2")]),
+            ("- 5", &[(Marks,"-"), (Digits,"5")]),
+        ];
+        for (text,kinds) in cases {
+            assert_eq!(tokens(text), expect(kinds), "{:#?} was split wrongly", text);
+        }
+    }
+
+    #[test]
+    fn every_mark_pair() {
+        let marks:Vec<char> = ".,;:?!\"'-".chars().collect();
+        for a in &marks {
+            for b in &marks {
+                let pair = format!("{a}{b}");
+                for text in [format!("hi{a}{b} yo"), format!("hi {a} {b} yo"), format!("hi {a}{b}yo")] {
+                    assert_eq!(tokens(&text), expect(&[(Letters,"hi"), (Marks,&pair), (Letters,"yo")]), "{:#?} was split wrongly", text);
+                }
+                assert_eq!(tokens(&format!("{a}{b}hi")), expect(&[(Marks,&pair), (Letters,"hi")]));
+                assert_eq!(tokens(&format!("hi{a}{b}")), expect(&[(Letters,"hi"), (Marks,&pair)]));
+            }
+        }
+        for c in &marks {
+            assert_eq!(to_mark(*c) == SEnd(*c), ".?!".contains(*c), "{:#?} ends sentences wrongly", c);
+        }
+    }
+
+    #[test]
+    fn whitespace() {
+        let plain = tokens("one two, 3");
+        for text in ["  one two, 3", "one two, 3  ", "one   two,   3", "one\ttwo,\t3", "one\ntwo,\n3", "one\r\ntwo,\r\n3", "\t\n one \t two , \n 3 \n"] {
+            assert_eq!(tokens(text), plain, "{:#?} was split wrongly", text);
+        }
+        assert_eq!(tokens(" \t\n "), Vec::new());
+        assert_eq!(tokens(""), Vec::new());
+    }
+
+    #[test]
+    fn number_notation() {
+        let cases:&[(&str,&[(TokenKind,&str)])] = &[
+            ("1,000 1,2", &[(Digits,"1,000"), (Digits,"1"), (Marks,","), (Digits,"2")]),
+            ("1,234,567.5", &[(Digits,"1,234,567.5")]),
+            ("1,0000", &[(Digits,"1"), (Marks,","), (Digits,"0000")]),
+            ("6.02e23 2e-5 5eggs", &[(Digits,"6.02e23"), (Digits,"2e-5"), (Digits,"5"), (Letters,"eggs")]),
+            ("3/4 -1/2, 50%.", &[(Digits,"3/4"), (Digits,"-1/2"), (Marks,","), (Digits,"50%"), (Marks,".")]),
+            ("1/-2 0x1f% 0x1e-5 0x1f/2", &[(Digits,"1/-2"), (Digits,"0x1f%"), (Digits,"0x1e-5"), (Digits,"0x1f/2")]),
+            ("0x1f 0b1010 -0o17 0x1e5", &[(Digits,"0x1f"), (Digits,"0b1010"), (Digits,"-0o17"), (Digits,"0x1e5")]),
+            ("0b2 10x1 0xg", &[(Digits,"0"), (Letters,"b"), (Digits,"2"), (Digits,"10"), (Letters,"x"), (Digits,"1"), (Digits,"0"), (Letters,"xg")]),
+            ("5bad 1e5f", &[(Digits,"5"), (Letters,"bad"), (Digits,"1e5"), (Letters,"f")]),
+        ];
+        for (text,kinds) in cases {
+            assert_eq!(tokens(text), expect(kinds), "{:#?} was split wrongly", text);
+        }
+        for (text,at) in [("1..5", 2), ("..5", 1), ("so 2...", 5), ("1.5..6", 4)] {
+            let chars:Vec<char> = text.chars().collect();
+            let error = lex(&chars, &ParseOptions::default(), &mut Vec::new()).unwrap_err();
+            assert!(matches!(error, GallifreyanError::InvalidNumber{offset:Offset{char, ..}, ..} if char == at), "{:#?} gave {:#?}", text, error);
+        }
+        assert_eq!(tokens("so... 5. ..."), expect(&[(Letters,"so"), (Marks,"..."), (Digits,"5"), (Marks,"....")]));
+        for text in ["a/b", "50 %", "3/ 4"] {
+            let chars:Vec<char> = text.chars().collect();
+            assert!(matches!(lex(&chars, &ParseOptions::default(), &mut Vec::new()), Err(GallifreyanError::InvalidCharacter{..})), "{:#?} should not be read", text);
+        }
+    }
+
+    #[test]
+    fn paragraphs() {
+        let cases:&[(&str,&[(TokenKind,&str)])] = &[
+            ("one\n\ntwo", &[(Letters,"one"), (Break,""), (Letters,"two")]),
+            ("one\r\n\r\ntwo", &[(Letters,"one"), (Break,""), (Letters,"two")]),
+            ("one \n \t\n\n\n two", &[(Letters,"one"), (Break,""), (Letters,"two")]),
+            ("one\ntwo", &[(Letters,"one"), (Letters,"two")]),
+            ("\n\none\n\n", &[(Letters,"one"), (Break,"")]),
+            ("end.\n\n, so", &[(Letters,"end"), (Marks,"."), (Break,""), (Marks,","), (Letters,"so")]),
+            ("end. \n ,", &[(Letters,"end"), (Marks,".,")]),
+        ];
+        for (text,kinds) in cases {
+            assert_eq!(tokens(text), expect(kinds), "{:#?} was split wrongly", text);
+        }
+    }
+
+    #[test]
+    fn positions() {
+        let chars:Vec<char> = "hi, 12 yo".chars().collect();
+        let starts:Vec<usize> = lex(&chars, &ParseOptions::default(), &mut Vec::new()).unwrap().iter().map(|t| t.start).collect();
+        assert_eq!(starts, vec![0,2,4,7]);
+        let chars:Vec<char> = "hi\u{263A}".chars().collect();
+        assert_eq!(lex(&chars, &ParseOptions::default(), &mut Vec::new()), Err(GallifreyanError::InvalidCharacter{character:'\u{263A}', offset:Offset::new(2,2)}));
+    }
+}
@@ -1,14 +1,84 @@
 mod tree;
+mod lexer;
+mod alphabet;
+mod phonetic;
+mod respell;
+mod normalize;
 mod conversion;
 mod shape;
 mod draw_word;
 mod draw_number;
 mod draw_sentence;
+mod draw_punctuation;
+mod draw_document;
+mod error;
+mod options;
+mod raster;
+mod vector;
+mod svg;
+mod xml;
+mod decode;
+mod legend;
 
-use conversion::get_image;
+use conversion::{get_image, get_image_from_phonemes, get_image_with_alphabet, get_image_with_parse_options, get_image_with_layout, get_image_with_options, get_text};
+
+//the parse tree, parse turns text into it and render draws it as an svg
+pub use tree::{Document, Paragraph, Sentence, WordTypes, PunctuationTypes, Word, Letter, Consonant, Vowel, Vowels, Arc, Marks, Number, Digit, Suffix};
+pub use conversion::{Rendered, WarningReport, parse, parse_with_alphabet, parse_with_options, parse_with_warnings, render, render_with_options, render_with_source, render_with_warnings, render_png, render_pdf, render_eps};
+pub use alphabet::Alphabet;
+pub use phonetic::{parse_phonemes, parse_phonemes_with_alphabet, Notation};
+pub use decode::{decode, decode_text, decode_with_alphabet, decode_text_with_alphabet};
+pub use legend::render_legend;
+pub use draw_document::Layout;
+pub use error::{GallifreyanError, Offset, Warning};
+pub use options::{ParseOptions, RenderOptions, Unknown};
+pub use respell::respell;
+pub use normalize::Accents;
+pub use raster::PngOptions;
+pub use vector::PageOptions;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub fn to_gallifreyan(text: &str) -> String {
     return get_image(text);
 }
+
+#[wasm_bindgen]
+pub fn to_gallifreyan_with_layout(text: &str, layout: Layout) -> String {
+    return get_image_with_layout(text, layout);
+}
+
+#[wasm_bindgen]
+pub fn to_gallifreyan_with_options(text: &str, options: &RenderOptions) -> String {
+    return get_image_with_options(text, options);
+}
+
+#[wasm_bindgen]
+pub fn to_gallifreyan_with_alphabet(text: &str, alphabet: &str, options: &RenderOptions) -> String {//alphabet is a letter table as json
+    return get_image_with_alphabet(text, alphabet, options);
+}
+
+#[wasm_bindgen]
+pub fn to_gallifreyan_with_parse_options(text: &str, parse: &ParseOptions, options: &RenderOptions) -> Rendered {
+    return get_image_with_parse_options(text, parse, options);
+}
+
+#[wasm_bindgen]
+pub fn respell_english(text: &str) -> String {//what respelling does to the text, to show alongside the drawing
+    return respell(text);
+}
+
+#[wasm_bindgen]
+pub fn phonemes_to_gallifreyan(text: &str, notation: Notation, options: &RenderOptions) -> String {
+    return get_image_from_phonemes(text, notation, options);
+}
+
+#[wasm_bindgen]
+pub fn from_gallifreyan(svg: &str) -> String {
+    return get_text(svg);
+}
+
+#[wasm_bindgen]
+pub fn gallifreyan_legend(options: &RenderOptions) -> String {
+    return render_legend(Alphabet::sherman(), options);
+}
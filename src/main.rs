@@ -1,57 +1,468 @@
-use std::f64::consts::PI;
+mod batch;
+
+use std::env;
+use std::fs;
 use std::io;
-mod conversion;
-mod tree;
-mod shape;
-mod draw_word;
-mod draw_number;
-mod draw_sentence;
+use std::io::prelude::*;
+use std::path::Path;
+use std::process::ExitCode;
 
-use conversion::get_image;
+use gallifreyan::{decode_text_with_alphabet, Accents, parse_phonemes_with_alphabet, parse_with_warnings, render_eps, render_legend, render_pdf, render_png, render_with_source, Alphabet, GallifreyanError, Layout, Notation, Offset, PageOptions, ParseOptions, PngOptions, RenderOptions, Unknown};
 
-use std::fs::File;
-use std::io::prelude::*;
+const USAGE:&str = "Usage: gallifreyan [OPTIONS] [TEXT]...
+       gallifreyan --batch <FILE> [OPTIONS]
+       gallifreyan --decode [--alphabet <FILE>] [--input <FILE>] [--output <FILE>]
+       gallifreyan --legend [--format <FORMAT>] [--output <FILE>]
+
+Converts text to Sherman's Gallifreyan. The text comes from the arguments,
+or from --input, or from stdin if neither is given. A blank line starts a new
+paragraph, drawn below the one before it.
+
+With --batch every line of FILE, or every row if it is a CSV file, is
+drawn to its own file in the output directory, named after its text.
+
+With --decode an svg made by this tool is read back and its text printed,
+exactly as it was written if the svg was made with --metadata.
+
+With --legend a chart of every letter, digraph and digit is drawn instead,
+as a svg or html page.
+
+Options:
+  -i, --input <FILE>     read the text from FILE, - for stdin
+  -o, --output <FILE>    write to FILE instead of stdout
+  -f, --format <FORMAT>  svg, html, png, pdf or eps, guessed from the output file if not given
+  -l, --layout <LAYOUT>  grid, row or spiral, for text with several sentences
+  -p, --phonetic <NOTATION>  read the text as ipa or arpabet sounds instead of spelling,
+                         with arpabet words in braces like {HH AH0 L OW1}
+      --accents <POLICY> strip, transliterate or error, for letters such as é or ß,
+                         transliterate by default
+      --unknown <POLICY> error, skip or replace, for characters that can't be drawn,
+                         error by default, the others print a warning for each
+      --replacement <C>  what --unknown replace draws instead, - by default
+      --respell          rewrite english spelling towards its sounds first, so c is k or s
+      --show-letters     print the letters being drawn to stderr, after any respelling
+  -a, --alphabet <FILE>  use the letter table in FILE, TOML or .json, see alphabets/sherman.toml
+      --metadata         keep the text in a svg's title, desc and metadata
+      --accessible       label a svg's sentences, words, letters and digits for screen readers
+      --titles           give each labelled part a title, shown on hover
+      --size <PIXELS>    how big a png is along its longer side, 1024 by default
+                         and 8192 at most
+      --dpi <DPI>        the resolution recorded in a png, 96 by default
+      --background <RGB> a hex colour such as ffffff behind a png, transparent by default
+      --page <SIZE>      page for a pdf or eps, a3, a4, a5, letter, legal or
+                         WIDTHxHEIGHT in millimetres, a4 by default
+      --margin <MM>      space left around the edge of the page, 12.7 by default
+  -b, --batch <FILE>     convert each line or CSV row of FILE separately
+      --csv              read the batch file as CSV, the default for .csv files
+      --column <N>       which CSV column holds the text, counting from 1
+      --header           skip the first row of the batch file
+  -d, --out-dir <DIR>    where batch files are written, the current directory by default
+      --decode           read an svg from --input or stdin and print its text
+      --legend           draw a chart of every letter and digit
+  -h, --help             print this help";
+
+#[derive(Debug,Copy,Clone,PartialEq)]
+enum Format {
+    Svg,
+    Html,
+    Png,
+    Pdf,
+    Eps,
+}
+impl TryFrom<&str> for Format {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "svg" => Ok(Format::Svg),
+            "html"|"htm" => Ok(Format::Html),
+            "png" => Ok(Format::Png),
+            "pdf" => Ok(Format::Pdf),
+            "eps"|"ps" => Ok(Format::Eps),
+            _ => Err(format!("{:#?} is not a format, expected svg, html, png, pdf or eps", value)),
+        }
+    }
+}
+
+#[derive(Debug,Default)]
+struct Args {
+    text: Vec<String>,
+    input: Option<String>,
+    output: Option<String>,
+    format: Option<Format>,
+    options: RenderOptions,
+    png: PngOptions,
+    page: PageOptions,
+    parse: ParseOptions,
+    show_letters: bool,
+    notation: Option<Notation>,
+    alphabet_file: Option<String>,
+    alphabet: Alphabet,
+    batch: Option<String>,
+    csv: bool,
+    column: Option<usize>,
+    header: bool,
+    out_dir: Option<String>,
+    decode: bool,
+    legend: bool,
+    help: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item=String>) -> Result<Args,String> {
+    let mut result = Args::default();
+    while let Some(arg) = args.next() {
+        let mut value = |name:&str| args.next().ok_or(format!("{name} needs a value"));
+        match arg.as_str() {
+            "-h"|"--help" => result.help = true,
+            "-i"|"--input" => result.input = Some(value(&arg)?),
+            "-o"|"--output" => result.output = Some(value(&arg)?),
+            "-f"|"--format" => result.format = Some(Format::try_from(value(&arg)?.as_str())?),
+            "-l"|"--layout" => result.options.layout = Layout::try_from(value(&arg)?.as_str())?,
+            "-p"|"--phonetic" => result.notation = Some(Notation::try_from(value(&arg)?.as_str())?),
+            "--accents" => result.parse.accents = Accents::try_from(value(&arg)?.as_str())?,
+            "--unknown" => result.parse.unknown = Unknown::try_from(value(&arg)?.as_str())?,
+            "--replacement" => {
+                let v = value(&arg)?;
+                let mut chars = v.chars();
+                result.parse.replacement = match (chars.next(), chars.next()) {
+                    (Some(c), None) => c.to_lowercase().next().unwrap_or(c),
+                    _ => return Err(format!("{:#?} is not a single character", v)),
+                };
+            },
+            "--respell" => result.parse.respell = true,
+            "--show-letters" => result.show_letters = true,
+            "-a"|"--alphabet" => result.alphabet_file = Some(value(&arg)?),
+            "--metadata" => result.options.metadata = true,
+            "--accessible" => result.options.accessible = true,
+            "--titles" => result.options.titles = true,
+            "--size" => {
+                let size = value(&arg)?;
+                result.png.size = size.parse().ok().filter(|s| *s > 0).ok_or(format!("{:#?} is not a size in pixels", size))?;
+                if result.png.size > PngOptions::MAX_SIZE {
+                    return Err(format!("{size} pixels is too big, a png can be at most {} pixels", PngOptions::MAX_SIZE));
+                }
+            },
+            "--dpi" => {
+                let dpi = value(&arg)?;
+                result.png.dpi = dpi.parse().ok().filter(|d:&f64| *d > 0.0).ok_or(format!("{:#?} is not a resolution", dpi))?;
+            },
+            "--background" => result.png.background = Some(parse_colour(&value(&arg)?)?),
+            "--page" => {
+                let margin = result.page.margin;
+                result.page = parse_page(&value(&arg)?)?;
+                result.page.margin = margin;
+            },
+            "--margin" => {
+                let margin = value(&arg)?;
+                result.page.margin = margin.parse().ok().filter(|m:&f64| *m >= 0.0).map(|m| PageOptions::from_mm(0.0, 0.0, m).margin)
+                    .ok_or(format!("{:#?} is not a margin in millimetres", margin))?;
+            },
+            "-b"|"--batch" => result.batch = Some(value(&arg)?),
+            "--csv" => result.csv = true,
+            "--column" => {
+                let column = value(&arg)?;
+                result.column = Some(column.parse().map_err(|_| format!("{:#?} is not a column number", column))?);
+            },
+            "--header" => result.header = true,
+            "-d"|"--out-dir" => result.out_dir = Some(value(&arg)?),
+            "--decode" => result.decode = true,
+            "--legend" => result.legend = true,
+            "--" => result.text.extend(args.by_ref()),
+            a if a.starts_with('-') && a.len() > 1 && !a[1..].starts_with(|c:char| c.is_ascii_digit()) => return Err(format!("unknown option {a}")),
+            _ => result.text.push(arg),
+        }
+    }
+    if result.input.is_some() && !result.text.is_empty() {
+        return Err("give the text as arguments or with --input, not both".to_string());
+    }
+    if result.batch.is_some() && (result.input.is_some() || !result.text.is_empty() || result.output.is_some()) {
+        return Err("--batch reads its own file and writes to --out-dir".to_string());
+    }
+    if result.decode && (result.batch.is_some() || !result.text.is_empty()) {
+        return Err("--decode reads the svg from --input or stdin".to_string());
+    }
+    if result.legend && (result.decode || result.batch.is_some() || result.input.is_some() || !result.text.is_empty()) {
+        return Err("--legend takes no text".to_string());
+    }
+    if result.legend && !matches!(get_format(&result), Format::Svg|Format::Html) {
+        return Err(format!("the legend can only be drawn as svg or html, not {}", extension(get_format(&result))));
+    }
+    return Ok(result);
+}
+
+fn parse_colour(value:&str) -> Result<[u8;3],String> {
+    let hex = value.trim_start_matches('#');
+    let channel = |i:usize| hex.get(i..i+2).and_then(|c| u8::from_str_radix(c, 16).ok());
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok([r, g, b]),
+        _ => Err(format!("{:#?} is not a colour, expected hex like ffffff", value)),
+    }
+}
+
+fn parse_page(value:&str) -> Result<PageOptions,String> {
+    if let Some(page) = PageOptions::named(value) {
+        return Ok(page);
+    }
+    let size = value.split_once('x').and_then(|(w,h)| Some((w.trim().parse::<f64>().ok()?, h.trim().parse::<f64>().ok()?)));
+    match size {
+        Some((w,h)) if w > 0.0 && h > 0.0 => Ok(PageOptions::from_mm(w, h, 0.0)),
+        _ => Err(format!("{:#?} is not a page, expected a4, letter or WIDTHxHEIGHT in millimetres", value)),
+    }
+}
+
+fn load_alphabet(path:&str) -> Result<Alphabet,String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("could not read {path}: {e}"))?;
+    let alphabet = if path.to_lowercase().ends_with(".json") {
+        Alphabet::from_json(&contents)
+    } else {
+        Alphabet::from_toml(&contents)
+    };
+    return alphabet.map_err(|e| format!("{path}: {e}"));
+}
+
+fn read_text(args:&Args) -> io::Result<String> {
+    if !args.text.is_empty() {
+        return Ok(args.text.join(" "));
+    }
+    return match args.input.as_deref() {
+        Some(path) if path != "-" => fs::read_to_string(path),
+        _ => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)?;
+            Ok(text)
+        }
+    };
+}
+
+fn extension(format:Format) -> &'static str {
+    match format {
+        Format::Svg => "svg",
+        Format::Html => "html",
+        Format::Png => "png",
+        Format::Pdf => "pdf",
+        Format::Eps => "eps",
+    }
+}
 
-use shape::{Cart,Polar};
-
-fn test() {
-    let tests = vec![(72, -56), (-57, 46), (-16, 23), (44, -26), (-61, 84), (-17, -79), (81, 2), (-5, 15), (86, 96), (-98, 75),
-                     (3,-4),(-3,-4),(-3,4),(3,4),
-                     (1,0),(0,-1),(-1,0),(0,1)];
-    for test in tests {
-        let (x,y) = test;
-        let mid = Polar::from(Cart::new(x as f64,y as f64));
-        let result = Cart::from(mid);
-        let result:(i32,i32) = (result.x.round() as i32,result.y.round() as i32);
-        let mid = mid.theta*180f64/PI;
-        let good = if  test == result{"PASS"} else {"FAILED"};
-        println!("{:?}->{:?}°->{:?}: {}",test,mid, result,good);
-    }
-}
-
-fn main() {
-    //return test();
-        println!("Enter text: ");
-		let mut input=String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
-        let output=get_image(&input);
-        let mut file = File::create("output.html").unwrap();
-        file.write_all(b"<!doctype html>
+fn get_format(args:&Args) -> Format {
+    if let Some(format) = args.format {
+        return format;
+    }
+    let extension = args.output.as_deref().and_then(|o| o.rsplit_once('.')).map(|(_,e)| e);
+    return extension.and_then(|e| Format::try_from(e).ok()).unwrap_or(Format::Svg);
+}
+
+fn to_html(svg:&str) -> String {
+    format!("<!doctype html>
 <html lang=\"en-US\">
   <head>
     <meta charset=\"utf-8\" />
     <title>Gallifreyan</title>
   </head>
   <body>
-	<h1>Welcome to David's circle</h1>
     <div style=\"width: 500px; height: 500px; stroke: #000000; fill: #000000\">
-").unwrap();
-    file.write_all(output.as_bytes()).unwrap();
-    file.write_all(b"
+{svg}
     </div>
   </body>
 </html>
-").unwrap();
+")
+}
+
+fn show_at(text:&str, offset:Offset, message:String) -> String {//point at the bad character on its line
+    let offset = offset.byte.min(text.len());
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i+1);
+    let line_end = text[offset..].find('\n').map_or(text.len(), |i| offset+i);
+    let column = text[line_start..offset].chars().count();
+    format!("{message}\n  {}\n  {}^", &text[line_start..line_end], " ".repeat(column))
+}
+
+fn show_error(text:&str, error:&GallifreyanError) -> String {
+    show_at(text, error.offset(), format!("error: {error}"))
+}
+
+fn render_text(text:&str, format:Format, args:&Args) -> Result<Vec<u8>,GallifreyanError> {
+    let document = match args.notation {
+        Some(notation) => parse_phonemes_with_alphabet(text, notation, &args.alphabet)?,
+        None => {
+            let (document,warnings) = parse_with_warnings(text, &args.alphabet, &args.parse)?;
+            for warning in warnings {
+                eprintln!("{}", show_at(text, warning.offset, format!("warning: {warning}")));
+            }
+            document
+        },
+    };
+    if args.show_letters {
+        eprintln!("{}", document.letter_sequence(&args.alphabet));
+    }
+    let output = match format {
+        Format::Svg => render_with_source(&document, text, &args.alphabet, &args.options)?.into_bytes(),
+        Format::Html => to_html(&render_with_source(&document, text, &args.alphabet, &args.options)?).into_bytes(),
+        Format::Png => render_png(&document, &args.options, &args.png)?,
+        Format::Pdf => render_pdf(&document, &args.options, &args.page)?,
+        Format::Eps => render_eps(&document, &args.options, &args.page)?.into_bytes(),
+    };
+    return Ok(output);
+}
+
+fn run_batch(args:&Args, path:&str) -> ExitCode {
+    let contents = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: could not read {path}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let csv = args.csv || path.to_lowercase().ends_with(".csv");
+    let rows = match batch::read_rows(&contents, csv, args.column.unwrap_or(1), args.header) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let out_dir = Path::new(args.out_dir.as_deref().unwrap_or("."));
+    let format = get_format(args);
+    let report = match batch::run(rows, out_dir, extension(format), |t| render_text(t, format, args)) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: could not write to {}: {e}", out_dir.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("{}", report.summary(out_dir));
+    return if report.failed.is_empty() {ExitCode::SUCCESS} else {ExitCode::FAILURE};
+}
+
+fn run_legend(args:&Args) -> ExitCode {
+    let legend = render_legend(&args.alphabet, &args.options);
+    let output = if get_format(args) == Format::Html {to_html(&legend)} else {legend};//parse_args only lets it be svg or html
+    if let Err(e) = write_output(args, output.as_bytes()) {
+        eprintln!("error: could not write the output: {e}");
+        return ExitCode::FAILURE;
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn write_output(args:&Args, bytes:&[u8]) -> io::Result<()> {
+    match args.output.as_deref() {
+        Some(path) if path != "-" => fs::write(path, bytes),
+        _ => io::stdout().write_all(bytes),
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = match parse_args(env::args().skip(1)) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    if args.help {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    if let Some(path) = &args.alphabet_file {
+        args.alphabet = match load_alphabet(path) {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+    }
+    if let Some(path) = &args.batch {
+        return run_batch(&args, path);
+    }
+    if args.legend {
+        return run_legend(&args);
+    }
+    let text = match read_text(&args) {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("error: could not read the text: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let rendered = if args.decode {
+        decode_text_with_alphabet(&text, &args.alphabet).map(|t| format!("{t}\n").into_bytes())
+    } else {
+        render_text(&text, get_format(&args), &args)
+    };
+    let output = match rendered {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{}", show_error(&text, &e));
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(e) = write_output(&args, &output) {
+        eprintln!("error: could not write the output: {e}");
+        return ExitCode::FAILURE;
+    }
+    return ExitCode::SUCCESS;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args:&[&str]) -> Result<Args,String> {
+        parse_args(args.iter().map(|a| a.to_string()))
     }
+
+    #[test]
+    fn options() {
+        let parsed = args(&["-o", "out.png", "--size", "2048", "-l", "spiral", "--respell", "--unknown", "replace", "--replacement", "X", "hello", "-5", "--", "--world"]).unwrap();
+        assert_eq!(parsed.text, vec!["hello", "-5", "--world"]);//a negative number is text, and everything after -- is too
+        assert_eq!(parsed.output.as_deref(), Some("out.png"));
+        assert_eq!(get_format(&parsed), Format::Png);
+        assert_eq!(parsed.png.size, 2048);
+        assert_eq!(parsed.options.layout, Layout::Spiral);
+        assert!(parsed.parse.respell);
+        assert_eq!((parsed.parse.unknown, parsed.parse.replacement), (Unknown::Replace, 'x'));
+
+        let parsed = args(&["-b", "in.csv", "--column", "2", "--header", "-d", "out", "-f", "eps", "--page", "100x50", "--margin", "0"]).unwrap();
+        assert_eq!((parsed.batch.as_deref(), parsed.column, parsed.header, parsed.out_dir.as_deref()), (Some("in.csv"), Some(2), true, Some("out")));
+        assert_eq!(get_format(&parsed), Format::Eps);
+        assert_eq!(parsed.page.margin, 0.0);
+        assert_eq!(get_format(&args(&["-o", "out.txt"]).unwrap()), Format::Svg);
+    }
+
+    #[test]
+    fn bad_options() {
+        let errors = [
+            (&["--output"][..], "--output needs a value"),
+            (&["--nope"], "unknown option --nope"),
+            (&["--size", "0"], "\"0\" is not a size in pixels"),
+            (&["--size", "10000"], "10000 pixels is too big, a png can be at most 8192 pixels"),
+            (&["--replacement", "ab"], "\"ab\" is not a single character"),
+            (&["--background", "fff"], "\"fff\" is not a colour, expected hex like ffffff"),
+            (&["-f", "gif"], "\"gif\" is not a format, expected svg, html, png, pdf or eps"),
+        ];
+        for (given,error) in errors {
+            assert_eq!(args(given).unwrap_err(), error, "{:#?}", given);
+        }
+    }
+
+    #[test]
+    fn conflicting_options() {
+        let errors = [
+            (&["-i", "in.txt", "hello"][..], "give the text as arguments or with --input, not both"),
+            (&["--batch", "in.txt", "hello"], "--batch reads its own file and writes to --out-dir"),
+            (&["--batch", "in.txt", "-i", "other.txt"], "--batch reads its own file and writes to --out-dir"),
+            (&["--batch", "in.txt", "-o", "out.svg"], "--batch reads its own file and writes to --out-dir"),
+            (&["--decode", "hello"], "--decode reads the svg from --input or stdin"),
+            (&["--decode", "--batch", "in.txt"], "--decode reads the svg from --input or stdin"),
+            (&["--legend", "hello"], "--legend takes no text"),
+            (&["--legend", "--decode"], "--legend takes no text"),
+            (&["--legend", "-f", "png"], "the legend can only be drawn as svg or html, not png"),
+            (&["--legend", "-o", "chart.pdf"], "the legend can only be drawn as svg or html, not pdf"),
+        ];
+        for (given,error) in errors {
+            assert_eq!(args(given).unwrap_err(), error, "{:#?}", given);
+        }
+        for given in [&["--legend", "-o", "chart.html"][..], &["--decode", "-i", "in.svg"], &["--batch", "in.txt", "-f", "png"]] {
+            assert!(args(given).is_ok(), "{:#?}", given);
+        }
+    }
+}
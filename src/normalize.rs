@@ -0,0 +1,97 @@
+use wasm_bindgen::prelude::*;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+//text from word processors and other languages, folded into the letters and marks Sherman has
+
+#[wasm_bindgen]
+#[derive(Debug,Clone,Copy,PartialEq,Default)]
+pub enum Accents {
+    Strip,//é becomes e, letters with nothing to strip back to, like ß, are still errors
+    #[default]
+    Transliterate,//as strip, and ß becomes ss, æ ae and so on
+    Error,//letters with accents are errors, as they always used to be
+}
+impl TryFrom<&str> for Accents {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "strip" => Ok(Accents::Strip),
+            "transliterate" => Ok(Accents::Transliterate),
+            "error" => Ok(Accents::Error),
+            _ => Err(format!("{:#?} is not an accent policy, expected strip, transliterate or error", value)),
+        }
+    }
+}
+
+const FOLDED:&[(char,&str)] = &[//typographic punctuation, whatever the policy
+    ('\u{2018}',"'"), ('\u{2019}',"'"), ('\u{201A}',"'"), ('\u{201B}',"'"), ('\u{2032}',"'"), ('`',"'"), ('\u{B4}',"'"),
+    ('\u{201C}',"\""), ('\u{201D}',"\""), ('\u{201E}',"\""), ('\u{201F}',"\""), ('\u{2033}',"\""), ('\u{AB}',"\""), ('\u{BB}',"\""),
+    ('\u{2010}',"-"), ('\u{2011}',"-"), ('\u{2012}',"-"), ('\u{2013}',"-"), ('\u{2014}',"-"), ('\u{2015}',"-"), ('\u{2212}',"-"),
+    ('\u{2026}',"..."),
+    ('\u{A0}'," "), ('\u{2007}'," "), ('\u{202F}'," "),
+];
+
+const TRANSLITERATED:&[(char,&str)] = &[//letters that don't decompose into a plain one
+    ('ß',"ss"), ('æ',"ae"), ('œ',"oe"), ('ø',"o"), ('þ',"th"), ('ð',"th"),
+    ('ł',"l"), ('đ',"d"), ('ħ',"h"), ('ı',"i"), ('ŋ',"ng"), ('ĳ',"ij"),
+];
+
+fn lookup(table:&[(char,&'static str)], c:char) -> Option<&'static str> {
+    table.iter().find(|(from,_)| *from == c).map(|(_,to)| *to)
+}
+
+pub(crate) fn normalize(c:char, accents:Accents) -> String {//what one char of the input becomes, before it is lowercased
+    if let Some(folded) = lookup(FOLDED, c) {
+        return folded.to_string();
+    }
+    if accents == Accents::Error {
+        return c.to_string();
+    }
+    let mut result = String::new();
+    for d in std::iter::once(c).nfkd().filter(|d| !is_combining_mark(*d)).flat_map(char::to_lowercase) {
+        match lookup(TRANSLITERATED, d) {
+            Some(t) if accents == Accents::Transliterate => result.push_str(t),
+            _ => result.push(d),
+        }
+    }
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::conversion::{parse, parse_with_options};
+    use crate::error::{GallifreyanError, Offset};
+    use crate::options::ParseOptions;
+
+    fn with(text:&str, accents:Accents) -> Result<crate::tree::Document,GallifreyanError> {
+        parse_with_options(text, Alphabet::sherman(), &ParseOptions{accents, ..ParseOptions::default()})
+    }
+
+    #[test]
+    fn folded() {
+        let same = [
+            ("Zoë and José", "zoe and jose"),
+            ("Ｆｕｌｌ ﬁsh", "full fish"),
+            ("cats\u{2019} toys", "cats' toys"),
+            ("wait\u{2014}what\u{2026}", "wait-what..."),
+            ("Straße Æsop", "strasse aesop"),
+            ("e\u{301}te\u{301}", "ete"),
+        ];
+        for (text,plain) in same {
+            assert_eq!(with(text, Accents::Transliterate).unwrap(), parse(plain).unwrap(), "{:#?} should be {:#?}", text, plain);
+        }
+        assert_eq!(with("Zoë", Accents::Strip).unwrap(), parse("zoe").unwrap());
+        assert_eq!(normalize('\u{201C}', Accents::Error), "\"");
+    }
+
+    #[test]
+    fn policies() {
+        assert_eq!(with("Straße", Accents::Strip), Err(GallifreyanError::InvalidCharacter{character:'ß', offset:Offset::new(4,4)}));
+        assert_eq!(with("José", Accents::Error), Err(GallifreyanError::InvalidCharacter{character:'é', offset:Offset::new(3,3)}));
+        assert_eq!(with("Æsop ☃", Accents::Transliterate), Err(GallifreyanError::InvalidCharacter{character:'☃', offset:Offset::new(6,5)}));
+        assert_eq!(with("cats\u{2019} toys", Accents::Error).unwrap(), parse("cats' toys").unwrap());
+    }
+}
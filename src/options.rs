@@ -0,0 +1,104 @@
+use wasm_bindgen::prelude::*;
+use crate::draw_document::Layout;
+use crate::normalize::Accents;
+use crate::shape::SENTENCE_RADIUS;
+
+#[wasm_bindgen]
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub struct RenderOptions {
+    pub sentence_radius: f64,
+    pub thin: f64,//stroke widths, as a fraction of the radius of whatever is being drawn
+    pub normal: f64,
+    pub thick: f64,
+    pub extra_thick: f64,
+    pub vowel_modifier: f64,//vowel size, relative to the space its letter gets
+    pub consonant_modifier: f64,
+    pub padding: f64,//space around each sentence, as a multiple of its radius
+    pub word_radius_divisor: f64,//how much smaller a word is than the most room it could have
+    pub layout: Layout,
+    pub metadata: bool,//embed the text in a title, desc and metadata block
+    pub accessible: bool,//give sentences, words, letters and digits a role and an aria-label
+    pub titles: bool,//and a title, which browsers also show on hover
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            sentence_radius: SENTENCE_RADIUS,
+            thin: 0.01,
+            normal: 0.02,
+            thick: 0.04,
+            extra_thick: 0.08,
+            vowel_modifier: 0.1,
+            consonant_modifier: 0.6,
+            padding: 1.1,
+            word_radius_divisor: 1.6,
+            layout: Layout::default(),
+            metadata: false,
+            accessible: false,
+            titles: false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl RenderOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn scale_strokes(&mut self, by:f64) {//below 1 for thin lines, above for bold
+        self.thin *= by;
+        self.normal *= by;
+        self.thick *= by;
+        self.extra_thick *= by;
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub struct ParseOptions {
+    pub respell: bool,//rewrite english spelling towards its sounds first, so cat and kat are the same
+    pub accents: Accents,//what happens to letters outside a to z
+    pub unknown: Unknown,//and to anything still left that can't be drawn
+    pub replacement: char,//what Unknown::Replace puts in its place
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            respell: false,
+            accents: Accents::default(),
+            unknown: Unknown::default(),
+            replacement: '-',
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug,Clone,Copy,PartialEq,Default)]
+pub enum Unknown {
+    #[default]
+    Error,//stop at the first one
+    Skip,//leave it out, with a warning
+    Replace,//draw the replacement instead, with a warning, or leave it out if that can't go there either
+}
+impl TryFrom<&str> for Unknown {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "error" => Ok(Unknown::Error),
+            "skip" => Ok(Unknown::Skip),
+            "replace" => Ok(Unknown::Replace),
+            _ => Err(format!("{:#?} is not a policy for unknown characters, expected error, skip or replace", value)),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl ParseOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
@@ -0,0 +1,280 @@
+use wasm_bindgen::prelude::*;
+
+use crate::alphabet::Alphabet;
+use crate::error::{GallifreyanError, Offset};
+use crate::lexer::{is_mark, to_mark};
+use crate::tree::{Document, Letter, Number, Paragraph, Sentence, Word, WordTypes};
+
+//reading pronunciations instead of spellings, each phoneme becomes the letter Sherman gives that sound
+
+#[wasm_bindgen]
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum Notation {
+    Ipa,
+    Arpabet,//CMU style, each word in braces, {DH AH0} {K AE1 T}
+}
+impl TryFrom<&str> for Notation {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "ipa" => Ok(Notation::Ipa),
+            "arpabet" => Ok(Notation::Arpabet),
+            _ => Err(format!("{:#?} is not a phonetic notation, expected ipa or arpabet", value)),
+        }
+    }
+}
+
+const IPA:&[(&str,&[&str])] = &[
+    ("tʃ",&["ch"]), ("ʧ",&["ch"]), ("dʒ",&["j"]), ("ʤ",&["j"]),
+    ("p",&["p"]), ("b",&["b"]), ("t",&["t"]), ("d",&["d"]), ("k",&["k"]), ("g",&["g"]), ("ɡ",&["g"]),
+    ("f",&["f"]), ("v",&["v"]), ("s",&["s"]), ("z",&["z"]), ("h",&["h"]),
+    ("m",&["m"]), ("n",&["n"]), ("l",&["l"]), ("ɫ",&["l"]), ("r",&["r"]), ("ɹ",&["r"]), ("ɾ",&["t"]),
+    ("w",&["w"]), ("ʍ",&["wh"]), ("j",&["y"]),
+    ("ʃ",&["sh"]), ("ʒ",&["j"]), ("θ",&["th"]), ("ð",&["th"]), ("ŋ",&["ng"]),
+    ("a",&["a"]), ("æ",&["a"]), ("ɑ",&["a"]), ("ɐ",&["a"]),
+    ("e",&["e"]), ("ɛ",&["e"]), ("ə",&["e"]), ("ɜ",&["e"]), ("ɚ",&["e","r"]), ("ɝ",&["e","r"]),
+    ("i",&["i"]), ("ɪ",&["i"]),
+    ("o",&["o"]), ("ɔ",&["o"]), ("ɒ",&["o"]),
+    ("u",&["u"]), ("ʊ",&["u"]), ("ʌ",&["u"]),
+];
+const IPA_IGNORED:&str = ".ˈˌ‿ˑ\u{361}\u{35C}\u{32F}";//syllable breaks, stress, links, tie bars and non syllabic marks
+const IPA_LONG:char = 'ː';//doubles the vowel before it
+
+const ARPABET:&[(&str,&[&str])] = &[
+    ("AA",&["a"]), ("AE",&["a"]), ("AH",&["u"]), ("AX",&["e"]), ("AO",&["o"]), ("AW",&["a","u"]), ("AY",&["a","i"]),
+    ("EH",&["e"]), ("ER",&["e","r"]), ("EY",&["e","i"]), ("IH",&["i"]), ("IY",&["i"]),
+    ("OW",&["o","u"]), ("OY",&["o","i"]), ("UH",&["u"]), ("UW",&["u"]),
+    ("B",&["b"]), ("CH",&["ch"]), ("D",&["d"]), ("DH",&["th"]), ("F",&["f"]), ("G",&["g"]), ("HH",&["h"]),
+    ("JH",&["j"]), ("K",&["k"]), ("L",&["l"]), ("M",&["m"]), ("N",&["n"]), ("NG",&["ng"]), ("P",&["p"]),
+    ("R",&["r"]), ("S",&["s"]), ("SH",&["sh"]), ("T",&["t"]), ("TH",&["th"]), ("V",&["v"]), ("W",&["w"]),
+    ("Y",&["y"]), ("Z",&["z"]), ("ZH",&["j"]),
+];
+
+const FUSED:&[(&str,&str,&str)] = &[("k","w","qu"), ("n","d","nd"), ("n","t","nt")];//sounds that Sherman writes as one letter
+
+struct Sound {
+    spelling: String,
+    phoneme: String,
+    offset: Offset,
+}
+
+fn lookup(table:&[(&str,&'static [&'static str])], phoneme:&str) -> Option<&'static [&'static str]> {
+    table.iter().find(|(p,_)| *p == phoneme).map(|(_,s)| *s)
+}
+
+fn sounds(phoneme:&str, spellings:&[&str], offset:Offset) -> Vec<Sound> {
+    spellings.iter().map(|s| Sound{spelling:s.to_string(), phoneme:phoneme.to_string(), offset}).collect()
+}
+
+fn ipa_sounds(chars:&[(Offset,char)]) -> Result<Vec<Sound>,GallifreyanError> {
+    let chars:Vec<(Offset,char)> = chars.iter().filter(|(_,c)| !IPA_IGNORED.contains(*c)).cloned().collect();
+    let mut result:Vec<Sound> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (offset,c) = chars[i];
+        if c == IPA_LONG {
+            if let Some(last) = result.last_mut() && "aeiou".contains(last.spelling.as_str()) {
+                last.spelling = last.spelling.repeat(2);
+            }
+            i += 1;
+            continue;
+        }
+        let pair = chars.get(i+1).map(|(_,n)| format!("{c}{n}"));
+        if let Some(pair) = pair && let Some(spellings) = lookup(IPA, &pair) {
+            result.append(&mut sounds(&pair, spellings, offset));
+            i += 2;
+        } else if let Some(spellings) = lookup(IPA, &c.to_string()) {
+            result.append(&mut sounds(&c.to_string(), spellings, offset));
+            i += 1;
+        } else {
+            return Err(GallifreyanError::InvalidPhoneme{phoneme:c.to_string(), offset});
+        }
+    }
+    return Ok(result);
+}
+
+fn arpabet_sounds(chars:&[(Offset,char)]) -> Result<Vec<Sound>,GallifreyanError> {
+    let mut result:Vec<Sound> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].1.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let offset = chars[i].0;
+        let mut symbol = String::new();
+        while i < chars.len() && !chars[i].1.is_whitespace() {
+            symbol.push(chars[i].1);
+            i += 1;
+        }
+        let upper = symbol.to_uppercase();
+        let bare = upper.trim_end_matches(['0','1','2']);//stress
+        let spellings = match bare {
+            "AH" if upper.ends_with('0') => Some(&["e"][..]),//unstressed, a schwa
+            _ => lookup(ARPABET, bare),
+        };
+        match spellings {
+            Some(spellings) => result.append(&mut sounds(&symbol, spellings, offset)),
+            None => return Err(GallifreyanError::InvalidPhoneme{phoneme:symbol, offset}),
+        }
+    }
+    return Ok(result);
+}
+
+fn to_word(mut sounds:Vec<Sound>, alphabet:&Alphabet) -> Result<Word,GallifreyanError> {
+    let mut i = 0;
+    while i+1 < sounds.len() {
+        if let Some((_,_,fused)) = FUSED.iter().find(|(a,b,_)| sounds[i].spelling == *a && sounds[i+1].spelling == *b) {
+            let second = sounds.remove(i+1);
+            sounds[i].spelling = fused.to_string();
+            sounds[i].phoneme += &second.phoneme;
+        }
+        i += 1;
+    }
+    let mut letters:Vec<Letter> = Vec::new();
+    for sound in sounds {
+        let chars:Vec<char> = sound.spelling.chars().collect();
+        let letter = match chars[..] {
+            [c] => alphabet.single(c),
+            [a,b] => alphabet.double(a,b),
+            _ => None,
+        };
+        match letter {
+            Some(l) => letters.push(l.clone()),
+            None => return Err(GallifreyanError::InvalidPhoneme{phoneme:sound.phoneme, offset:sound.offset}),
+        }
+    }
+    return Ok(Word::joined(letters));
+}
+
+fn ends_ipa(chars:&[(Offset,char)], i:usize) -> bool {//a full stop between sounds only splits syllables
+    let c = chars[i].1;
+    let next = chars.get(i+1).map(|(_,n)| *n);
+    if c == '.' {
+        return !next.is_some_and(|n| !n.is_whitespace() && !is_mark(n) && !"/[]".contains(n));
+    }
+    return c.is_whitespace() || c.is_ascii_digit() || is_mark(c) || "/[]".contains(c);
+}
+
+pub fn parse_phonemes(text: &str, notation: Notation) -> Result<Document, GallifreyanError> {
+    return parse_phonemes_with_alphabet(text, notation, Alphabet::sherman());
+}
+
+pub fn parse_phonemes_with_alphabet(text: &str, notation: Notation, alphabet: &Alphabet) -> Result<Document, GallifreyanError> {
+    let chars:Vec<(Offset,char)> = text.char_indices().enumerate().map(|(char,(byte,c))| (Offset::new(byte,char),c)).collect();
+    let mut paragraphs:Vec<Vec<WordTypes>> = Vec::new();
+    let mut words:Vec<WordTypes> = Vec::new();
+    let mut newlines = 0;//since the last word, a blank line starts a new paragraph as in spelt text
+    let mut i = 0;
+    while i < chars.len() {
+        let (offset,c) = chars[i];
+        let next = chars.get(i+1).map(|(_,n)| *n);
+        if c.is_whitespace() || (notation == Notation::Ipa && "/[]".contains(c)) {
+            newlines += if c == '\n' {1} else {0};
+            if newlines == 2 && !words.is_empty() {
+                paragraphs.push(std::mem::take(&mut words));
+            }
+            i += 1;
+            continue;
+        }
+        newlines = 0;
+        if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].1.is_ascii_digit() || (chars[i].1 == '.' && chars.get(i+1).is_some_and(|(_,n)| n.is_ascii_digit()))) {
+                i += 1;
+            }
+            let digits:Vec<char> = chars[start..i].iter().map(|(_,c)| *c).collect();
+            words.push(WordTypes::Number(Number::try_from(digits).map_err(|e| e.shift(offset))?));
+        } else if is_mark(c) && (notation != Notation::Ipa || ends_ipa(&chars, i)) {
+            match words.last_mut() {//marks only have spaces between them, so they go together as in spelt text
+                Some(WordTypes::Punctuation(marks)) if chars[i-1].1.is_whitespace() || is_mark(chars[i-1].1) => marks.push(to_mark(c)),
+                _ => words.push(WordTypes::Punctuation(vec![to_mark(c)])),
+            }
+            i += 1;
+        } else {
+            let sounds = match notation {
+                Notation::Ipa => {
+                    let start = i;
+                    while i < chars.len() && !ends_ipa(&chars, i) {
+                        i += 1;
+                    }
+                    ipa_sounds(&chars[start..i])?
+                },
+                Notation::Arpabet => {
+                    if c != '{' {
+                        return Err(GallifreyanError::InvalidCharacter{character:c, offset});
+                    }
+                    let end = chars[i..].iter().position(|(_,c)| *c == '}')
+                        .ok_or(GallifreyanError::InvalidPhoneme{phoneme:"{".to_string(), offset})?;
+                    let sounds = arpabet_sounds(&chars[i+1..i+end])?;
+                    i += end+1;
+                    sounds
+                },
+            };
+            if !sounds.is_empty() {
+                words.push(WordTypes::PlainWord(to_word(sounds, alphabet)?));
+            }
+        }
+    }
+    paragraphs.push(words);
+    let mut paragraphs:Vec<Paragraph> = paragraphs.into_iter()
+        .map(|words| Paragraph{sentences: Sentence{words}.split()})
+        .filter(|p| !p.sentences.is_empty())
+        .collect();
+    if paragraphs.is_empty() {
+        paragraphs.push(Paragraph{sentences: Vec::new()});
+    }
+    return Ok(Document{paragraphs});
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversion::parse;
+
+    #[test]
+    fn sounds_become_letters() {
+        let same = [
+            ("ðə kæt sæt.", Notation::Ipa, "the kat sat."),
+            ("/ˈfəʊn/", Notation::Ipa, "feun"),
+            ("kwiːn t͡ʃɜːtʃ!", Notation::Ipa, "quiin cheech!"),
+            ("ˈhæ.pi ˈɪŋ.ɡlɪʃ", Notation::Ipa, "hapi ingglish"),
+            ("{DH AH0} {K AE1 T} {S AE1 T}.", Notation::Arpabet, "the kat sat."),
+            ("{F OW1 N} 42 {HH AE1 N D Z}?", Notation::Arpabet, "foun 42 handz?"),
+        ];
+        for (phonemes,notation,spelling) in same {
+            assert_eq!(parse_phonemes(phonemes, notation).unwrap(), parse(spelling).unwrap(), "{:#?} should be {:#?}", phonemes, spelling);
+        }
+    }
+
+    #[test]
+    fn paragraphs() {
+        let same = [
+            ("ðə kæt.\n\nsæt", Notation::Ipa, "the kat.\n\nsat"),
+            ("ðə\nkæt \n \r\n\n sæt", Notation::Ipa, "the\nkat\n\nsat"),
+            ("{K AE1 T}\n  \n{S AE1 T}!\n", Notation::Arpabet, "kat\n\nsat!"),
+        ];
+        for (phonemes,notation,spelling) in same {
+            let document = parse_phonemes(phonemes, notation).unwrap();
+            assert_eq!(document, parse(spelling).unwrap(), "{:#?} should be {:#?}", phonemes, spelling);
+            assert_eq!(document.paragraphs.len(), 2);
+        }
+        assert_eq!(parse_phonemes("ðə\nkæt", Notation::Ipa).unwrap().paragraphs.len(), 1);
+        assert_eq!(parse_phonemes("\n\n", Notation::Ipa).unwrap().paragraphs.len(), 1);
+    }
+
+    #[test]
+    fn unknown_sounds() {
+        let bad = [
+            ("kæʔ", Notation::Ipa, "ʔ", 2),
+            ("{K XX T}", Notation::Arpabet, "XX", 3),
+            ("{K AE1 T", Notation::Arpabet, "{", 0),
+        ];
+        for (phonemes,notation,phoneme,char) in bad {
+            let error = parse_phonemes(phonemes, notation).unwrap_err();
+            assert_eq!(error, GallifreyanError::InvalidPhoneme{phoneme:phoneme.to_string(), offset:Offset::new(phonemes.char_indices().nth(char).unwrap().0, char)});
+        }
+        assert!(matches!(parse_phonemes("cat", Notation::Arpabet), Err(GallifreyanError::InvalidCharacter{character:'c', ..})));
+    }
+}
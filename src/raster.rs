@@ -0,0 +1,256 @@
+use std::f64::consts::TAU;
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use crate::shape::{Arc, Cart, Circle, Line, Renderer};
+
+const INCHES_PER_METRE:f64 = 39.3701;
+
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub struct PngOptions {
+    pub size: u32,//pixels along the longer side, at most MAX_SIZE
+    pub dpi: f64,
+    pub background: Option<[u8;3]>,//transparent if none
+}
+impl PngOptions {
+    pub const MAX_SIZE:u32 = 8192;//any bigger and the coverage alone takes gigabytes
+}
+impl Default for PngOptions {
+    fn default() -> Self {
+        Self { size: 1024, dpi: 96.0, background: None }
+    }
+}
+
+fn coverage(distance:f64) -> f64 {//how much of a pixel is covered by an edge this far inside it, in pixels
+    (distance+0.5).clamp(0.0, 1.0)
+}
+
+pub struct Raster {
+    width: u32,
+    height: u32,
+    scale: f64,//pixels per unit of the drawing
+    alpha: Vec<f64>,//everything is drawn in one colour, so only coverage is kept
+}
+
+impl Raster {
+    pub fn new(size:Cart, pixels:u32) -> Self {
+        let longest = size.x.max(size.y);
+        let scale = pixels.min(PngOptions::MAX_SIZE) as f64/longest;
+        let width = ((size.x*scale).round() as u32).max(1);
+        let height = ((size.y*scale).round() as u32).max(1);
+        Self { width, height, scale, alpha: vec![0.0; width as usize*height as usize] }
+    }
+
+    fn paint(&mut self, x:u32, y:u32, cover:f64) {
+        if cover <= 0.0 {
+            return;
+        }
+        let a = &mut self.alpha[y as usize*self.width as usize+x as usize];
+        *a += cover*(1.0-*a);
+    }
+
+    fn each_pixel<F>(&mut self, min:Cart, max:Cart, cover:F) where F: Fn(Cart) -> f64 {//cover is given each pixel centre in drawing units
+        let x0 = ((min.x*self.scale).floor().max(0.0)) as u32;
+        let y0 = ((min.y*self.scale).floor().max(0.0)) as u32;
+        let x1 = ((max.x*self.scale).ceil().max(0.0) as u32).min(self.width);
+        let y1 = ((max.y*self.scale).ceil().max(0.0) as u32).min(self.height);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let centre = Cart::new((x as f64+0.5)/self.scale, (y as f64+0.5)/self.scale);
+                let c = cover(centre);
+                self.paint(x, y, c);
+            }
+        }
+    }
+
+    fn around(&self, centre:Cart, reach:f64) -> (Cart,Cart) {
+        let reach = reach+1.0/self.scale;
+        (Cart::new(centre.x-reach, centre.y-reach), Cart::new(centre.x+reach, centre.y+reach))
+    }
+
+    fn fill_circle(&mut self, centre:Cart, radius:f64) {
+        let (min,max) = self.around(centre, radius);
+        let scale = self.scale;
+        self.each_pixel(min, max, |p| coverage((radius-p.distance(&centre))*scale));
+    }
+
+    fn stroke_circle(&mut self, centre:Cart, radius:f64, width:f64) {
+        let (min,max) = self.around(centre, radius+width/2.0);
+        let scale = self.scale;
+        self.each_pixel(min, max, |p| coverage((width/2.0-(p.distance(&centre)-radius).abs())*scale));
+    }
+
+    fn stroke_arc(&mut self, centre:Cart, radius:f64, start:f64, sweep:f64, width:f64) {
+        //angles are measured the way the svg sees them, with y pointing down, sweep is negative for the other way round
+        let (min,max) = self.around(centre, radius+width/2.0);
+        let scale = self.scale;
+        self.each_pixel(min, max, |p| {
+            let across = coverage((width/2.0-(p.distance(&centre)-radius).abs())*scale);
+            let angle = (p.y-centre.y).atan2(p.x-centre.x);
+            let along = if sweep >= 0.0 {angle-start} else {start-angle}.rem_euclid(TAU);
+            let length = sweep.abs();
+            let inside = if along <= length {
+                along.min(length-along)
+            } else {
+                -(along-length).min(TAU-along)
+            };
+            across*coverage(inside*radius*scale)
+        });
+    }
+
+    fn stroke_line(&mut self, start:Cart, end:Cart, width:f64) {
+        let length = start.distance(&end);
+        if length == 0.0 {
+            return;
+        }
+        let dir = Cart::new((end.x-start.x)/length, (end.y-start.y)/length);
+        let min = Cart::new(start.x.min(end.x)-width, start.y.min(end.y)-width);
+        let max = Cart::new(start.x.max(end.x)+width, start.y.max(end.y)+width);
+        let scale = self.scale;
+        self.each_pixel(min, max, |p| {
+            let rel = start.to(&p);
+            let along = rel.x*dir.x + rel.y*dir.y;
+            let across = (rel.x*dir.y - rel.y*dir.x).abs();
+            let inside = along.min(length-along);
+            coverage((width/2.0-across)*scale)*coverage(inside*scale)
+        });
+    }
+
+    fn rgba(&self, background:Option<[u8;3]>) -> Vec<u8> {
+        let mut pixels:Vec<u8> = Vec::with_capacity(self.alpha.len()*4);
+        for a in &self.alpha {
+            let a = a.clamp(0.0, 1.0);
+            match background {
+                Some(bg) => {
+                    pixels.extend(bg.iter().map(|c| (*c as f64*(1.0-a)).round() as u8));
+                    pixels.push(255);
+                },
+                None => pixels.extend([0, 0, 0, (a*255.0).round() as u8]),
+            }
+        }
+        return pixels;
+    }
+
+    pub fn to_png(&self, png:&PngOptions) -> Vec<u8> {
+        let pixels = self.rgba(png.background);
+        let row = self.width as usize*4;
+        let mut filtered:Vec<u8> = Vec::with_capacity(pixels.len()+self.height as usize);
+        for line in pixels.chunks(row) {
+            filtered.push(0);//no filter
+            filtered.extend_from_slice(line);
+        }
+
+        let mut header:Vec<u8> = Vec::new();
+        header.extend(self.width.to_be_bytes());
+        header.extend(self.height.to_be_bytes());
+        header.extend([8, 6, 0, 0, 0]);//8 bit rgba, deflate, standard filters, not interlaced
+
+        let per_metre = (png.dpi*INCHES_PER_METRE).round() as u32;
+        let mut physical:Vec<u8> = Vec::new();
+        physical.extend(per_metre.to_be_bytes());
+        physical.extend(per_metre.to_be_bytes());
+        physical.push(1);//in metres
+
+        let mut result:Vec<u8> = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        push_chunk(&mut result, b"IHDR", &header);
+        push_chunk(&mut result, b"pHYs", &physical);
+        push_chunk(&mut result, b"IDAT", &compress_to_vec_zlib(&filtered, 6));
+        push_chunk(&mut result, b"IEND", &[]);
+        return result;
+    }
+}
+
+impl Renderer for Raster {
+    fn circle(&mut self, circle:&Circle) {
+        match circle.thickness {
+            Some(t) => self.stroke_circle(circle.centre, circle.radius, t),
+            None => self.fill_circle(circle.centre, circle.radius),
+        }
+    }
+    fn arc(&mut self, arc:&Arc) {
+        let (centre,radius,start,sweep) = arc.centre_and_angles();
+        self.stroke_arc(centre, radius, start, sweep, arc.thickness);
+    }
+    fn line(&mut self, line:&Line) {
+        self.stroke_line(line.start, line.end, line.thickness);
+    }
+}
+
+fn crc32(bytes:&[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for b in bytes {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {(crc >> 1) ^ 0xedb8_8320} else {crc >> 1};
+        }
+    }
+    return !crc;
+}
+
+fn push_chunk(png:&mut Vec<u8>, kind:&[u8;4], data:&[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miniz_oxide::inflate::decompress_to_vec_zlib;
+
+    fn number(bytes:&[u8]) -> u32 {
+        u32::from_be_bytes(bytes[..4].try_into().unwrap())
+    }
+
+    fn chunks(png:&[u8]) -> Vec<(String,Vec<u8>)> {
+        let mut chunks = Vec::new();
+        let mut i = 8;
+        while i < png.len() {
+            let length = number(&png[i..]) as usize;
+            let end = i+8+length;
+            assert_eq!(crc32(&png[i+4..end]), number(&png[end..]));
+            chunks.push((String::from_utf8(png[i+4..i+8].to_vec()).unwrap(), png[i+8..end].to_vec()));
+            i = end+4;
+        }
+        return chunks;
+    }
+
+    fn pixel(png:&[u8], x:usize, y:usize) -> [u8;4] {
+        let chunks = chunks(png);
+        let width = number(&chunks[0].1) as usize;
+        let data = decompress_to_vec_zlib(&chunks[2].1).unwrap();
+        let start = y*(width*4+1)+1+x*4;//each row starts with its filter
+        return data[start..start+4].try_into().unwrap();
+    }
+
+    #[test]
+    fn header() {
+        let raster = Raster::new(Cart::new(20.0, 10.0), 40);
+        let png = raster.to_png(&PngOptions{size:40, dpi:300.0, background:None});
+        assert_eq!(png[..8], [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']);
+        let chunks = chunks(&png);
+        assert_eq!(chunks.iter().map(|c| c.0.as_str()).collect::<Vec<_>>(), vec!["IHDR", "pHYs", "IDAT", "IEND"]);
+        let header = &chunks[0].1;
+        assert_eq!((number(&header[0..]), number(&header[4..])), (40, 20));
+        assert_eq!(header[8..], [8, 6, 0, 0, 0]);
+        let physical = &chunks[1].1;
+        assert_eq!((number(&physical[0..]), number(&physical[4..]), physical[8]), (11811, 11811, 1));
+        assert_eq!((number(physical) as f64/INCHES_PER_METRE).round(), 300.0);
+    }
+
+    #[test]
+    fn pixels() {
+        let mut raster = Raster::new(Cart::new(10.0, 10.0), 10);//a pixel for each unit
+        raster.circle(&Circle::new(Cart::new(5.0, 5.0), 3.0, Some(2.0)));
+        let png = raster.to_png(&PngOptions::default());
+        assert_eq!(pixel(&png, 4, 2), [0, 0, 0, 255]);//on the stroke
+        assert_eq!(pixel(&png, 4, 4), [0, 0, 0, 0]);//inside the circle
+        assert_eq!(pixel(&png, 0, 0), [0, 0, 0, 0]);
+
+        let png = raster.to_png(&PngOptions{background:Some([255, 128, 0]), ..PngOptions::default()});
+        assert_eq!(pixel(&png, 4, 2), [0, 0, 0, 255]);
+        assert_eq!(pixel(&png, 4, 4), [255, 128, 0, 255]);
+        assert_eq!(pixel(&png, 9, 9), [255, 128, 0, 255]);
+    }
+}
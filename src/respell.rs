@@ -0,0 +1,174 @@
+//english spelling rewritten towards how it sounds, before a word is split into Sherman's letters
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+enum When {
+    Anywhere,
+    Start,//only at the start of the word
+    Front,//only before e, i or y, where c goes soft
+    Silent,//a final e after a consonant, once the word already has a vowel sound
+}
+use When::*;
+
+const RULES:&[(&str,&str,When)] = &[//tried in order at each position, the first that fits is used
+    ("ch","ch",Anywhere),//already one of Sherman's letters, so the c rules leave it alone
+    ("ck","k",Anywhere),
+    ("cc","ks",Front),
+    ("cc","k",Anywhere),
+    ("xc","ks",Front),//the c goes into the x's s sound, as in excellent
+    ("sc","s",Front),
+    ("c","s",Front),
+    ("c","k",Anywhere),
+    ("dge","j",Anywhere),
+    ("ph","f",Anywhere),
+    ("kn","n",Start),
+    ("wr","r",Start),
+    ("x","z",Start),
+    ("x","ks",Anywhere),
+    ("e","",Silent),
+    ("bb","b",Anywhere),
+    ("dd","d",Anywhere),
+    ("ff","f",Anywhere),
+    ("gg","g",Anywhere),
+    ("ll","l",Anywhere),
+    ("mm","m",Anywhere),
+    ("nn","n",Anywhere),
+    ("pp","p",Anywhere),
+    ("rr","r",Anywhere),
+    ("ss","s",Anywhere),
+    ("tt","t",Anywhere),
+    ("zz","z",Anywhere),
+];
+
+const EXCEPTIONS:&[(&str,&str)] = &[//common words the rules get wrong, used as they are
+    ("answer","anser"),
+    ("celtic","keltik"),
+    ("cello","chelo"),
+    ("choir","kwier"),
+    ("come","kum"),
+    ("could","kud"),
+    ("done","dun"),
+    ("eye","i"),
+    ("friend","frend"),
+    ("island","iland"),
+    ("listen","lisen"),
+    ("none","nun"),
+    ("ocean","oshen"),
+    ("of","ov"),
+    ("once","wuns"),
+    ("one","wun"),
+    ("said","sed"),
+    ("says","sez"),
+    ("sceptic","skeptik"),
+    ("should","shud"),
+    ("some","sum"),
+    ("two","tu"),
+    ("who","hoo"),
+    ("whose","hooz"),
+    ("women","wimin"),
+    ("would","wud"),
+];
+
+fn is_vowel(c:char) -> bool {
+    "aeiouy".contains(c)
+}
+
+fn fits(word:&[char], i:usize, from:&str, when:When) -> bool {
+    let from:Vec<char> = from.chars().collect();
+    if !word[i..].starts_with(&from) {
+        return false;
+    }
+    let after = word.get(i+from.len()).copied();
+    match when {
+        Anywhere => true,
+        Start => i == 0,
+        Front => after.is_some_and(|c| "eiy".contains(c)),
+        Silent => after.is_none_or(|c| c == '\'') && i >= 2 && !is_vowel(word[i-1]) && word[..i-1].iter().any(|c| is_vowel(*c)),
+    }
+}
+
+pub(crate) fn respell_word(word:&[char]) -> (Vec<char>,Vec<usize>) {//the new spelling, and where in the old one each of its chars came from
+    let text:String = word.iter().collect();
+    if let Some((_,to)) = EXCEPTIONS.iter().find(|(from,_)| *from == text) {
+        return (to.chars().collect(), vec![0; to.chars().count()]);
+    }
+    let mut chars:Vec<char> = Vec::new();
+    let mut origins:Vec<usize> = Vec::new();
+    let mut i = 0;
+    while i < word.len() {
+        match RULES.iter().find(|(from,_,when)| fits(word, i, from, *when)) {
+            Some((from,to,_)) => {
+                chars.extend(to.chars());
+                origins.extend(to.chars().map(|_| i));
+                i += from.chars().count();
+            },
+            None => {
+                chars.push(word[i]);
+                origins.push(i);
+                i += 1;
+            }
+        }
+    }
+    return (chars,origins);
+}
+
+pub fn respell(text:&str) -> String {//every word of the text as it would be drawn with respelling on
+    let mut result = String::new();
+    let mut word:Vec<char> = Vec::new();
+    for c in text.to_lowercase().chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_lowercase() || (c == '\'' && !word.is_empty()) {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            result.extend(respell_word(&word).0);
+            word.clear();
+        }
+        result.push(c);
+    }
+    result.pop();
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::conversion::{parse, parse_with_options};
+    use crate::options::ParseOptions;
+
+    #[test]
+    fn rules_and_exceptions() {
+        let words = [
+            ("cat", "kat"),
+            ("city", "sity"),
+            ("chance", "chans"),
+            ("back", "bak"),
+            ("accept", "aksept"),
+            ("science", "siens"),
+            ("box", "boks"),
+            ("xylophone", "zylofon"),
+            ("excellent", "ekselent"),
+            ("exceed", "ekseed"),
+            ("excuse", "ekskus"),
+            ("make", "mak"),
+            ("the", "the"),
+            ("tree", "tree"),
+            ("knight", "night"),
+            ("edge", "ej"),
+            ("little", "litl"),
+            ("one", "wun"),
+            ("don't", "don't"),
+        ];
+        for (word,respelt) in words {
+            assert_eq!(respell(word), respelt, "{:#?} was respelt wrongly", word);
+        }
+        assert_eq!(respell("The quick cat, once."), "the quik kat, wuns.");
+    }
+
+    #[test]
+    fn respelt_before_parsing() {
+        let options = ParseOptions{respell: true, ..ParseOptions::default()};
+        assert_eq!(parse_with_options("Cats accept", Alphabet::sherman(), &options).unwrap(), parse("kats aksept").unwrap());
+        assert_ne!(parse_with_options("cat", Alphabet::sherman(), &ParseOptions::default()).unwrap(), parse("kat").unwrap());
+    }
+}
@@ -2,14 +2,20 @@ use std::convert::From;
 use std::boxed::Box;
 use std::f64::consts::PI;
 use std::f64::consts::TAU;
+use crate::options::RenderOptions;
 
 pub const SENTENCE_RADIUS:f64 = 100.0;
 
-const FILL:&str="current";
-const STROKE:&str="current";
 pub trait Shape {
     fn shove(&mut self, diff:Cart);
-    fn to_element(&self) -> String;
+    fn draw(&self, renderer:&mut dyn Renderer);
+}
+pub trait Renderer {//each output format, or anything else that walks the drawing, implements this
+    fn circle(&mut self, circle:&Circle);
+    fn arc(&mut self, arc:&Arc);
+    fn line(&mut self, line:&Line);
+    fn begin_group(&mut self, _group:&ShapeSet) {}
+    fn end_group(&mut self, _group:&ShapeSet) {}
 }
 pub type Shapes = Vec<BShape>;
 pub type BShape = Box<dyn Shape>;
@@ -22,12 +28,12 @@ pub enum Thickness {
     ExtraThick,
 }
 impl Thickness {
-    pub fn val(&self,mult:f64) -> f64 {
+    pub fn val(&self,mult:f64,opts:&RenderOptions) -> f64 {
         match self {
-            Thin => mult*0.01,
-            Normal => mult*0.02,
-            Thick => mult*0.04,
-            ExtraThick => mult*0.08,
+            Thin => mult*opts.thin,
+            Normal => mult*opts.normal,
+            Thick => mult*opts.thick,
+            ExtraThick => mult*opts.extra_thick,
         }
     }
 }
@@ -120,6 +126,8 @@ impl From<Cart> for Polar {
 pub struct ShapeSet {
     shapes: Shapes,
     class: String,
+    label: Option<String>,//what the group says, for screen readers
+    role: &'static str,
 }
 const WORD_PULSE:bool = false;
 const SENTENCE_PULSE:bool = false;
@@ -128,12 +136,12 @@ impl ShapeSet {
     pub fn new_rotating(shapes: Shapes, is_clockwise:bool) -> Self {
         let direction = if is_clockwise {"clockwise"} else {"anti_clockwise"};
         let class = format!("{direction}_number");
-        Self { shapes,class }
+        Self { shapes,class,label:None,role:"group" }
     }
     pub fn new_rotating_class(shapes: Shapes, is_clockwise:bool,class:&str) -> Self {
         let direction = if is_clockwise {"clockwise"} else {"anti_clockwise"};
         let class = format!("{direction}_number {class}");
-        Self { shapes,class }
+        Self { shapes,class,label:None,role:"group" }
     }
     pub fn new(shapes:Shapes, class:&str) -> Self {
         let mut class = class.to_string();
@@ -143,7 +151,21 @@ impl ShapeSet {
         if SENTENCE_PULSE && class.contains("sentence") && !class.contains("pulsing") {
             class = format!("{class} pulsing");
         }
-        Self { shapes,class }
+        Self { shapes,class,label:None,role:"group" }
+    }
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+    pub fn with_label(mut self, label:String, role:&'static str) -> Self {//role is group for things made of other labelled things, img otherwise
+        self.label = Some(label);
+        self.role = role;
+        self
+    }
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+    pub fn role(&self) -> &str {
+        self.role
     }
 }
 
@@ -151,17 +173,17 @@ impl Shape for ShapeSet {
     fn shove(&mut self, diff:Cart) {
         let _ = &self.shapes.iter_mut().for_each(|s| s.shove(diff));
     }
-    fn to_element(&self) -> String {
-        let els = &self.shapes.iter().map(|s| s.to_element()).collect::<Vec<_>>().join("\n");
-        let class = &self.class;
-        format!("<g class=\"{class}\">{els}</g>")
+    fn draw(&self, renderer:&mut dyn Renderer) {
+        renderer.begin_group(self);
+        self.shapes.iter().for_each(|s| s.draw(renderer));
+        renderer.end_group(self);
     }
 }
 
 pub struct Circle {
-    centre: Cart,
-    radius:f64,
-    thickness:Option<f64>, // no thickness indicates fill
+    pub centre: Cart,
+    pub radius:f64,
+    pub thickness:Option<f64>, // no thickness indicates fill
 }
 
 impl Circle {
@@ -174,33 +196,47 @@ impl Shape for Circle {
     fn shove(&mut self, diff:Cart) {
         self.centre.shove(diff);
     }
-    fn to_element(&self) -> String {
-        let (opacity,width) = match &self.thickness {
-            Some(t) => (0.0,*t),
-            None => (1.0,0.0)
-        };
-        return format!("<circle  cx=\"{}\" cy=\"{}\" r=\"{}\" stroke-width=\"{}\" fill-opacity=\"{}\" />",
-                       self.centre.x,
-                       self.centre.y,
-                       self.radius,
-                       width,
-                       opacity
-        )
+    fn draw(&self, renderer:&mut dyn Renderer) {
+        renderer.circle(self);
     }
 }
 pub struct Arc {
-    start: Cart,
-    end: Cart,
-    radius:f64,
-    large:bool,//do we take the long way round
-    clockwise:bool,
-    thickness:f64,
+    pub start: Cart,
+    pub end: Cart,
+    pub radius:f64,
+    pub large:bool,//do we take the long way round
+    pub clockwise:bool,
+    pub thickness:f64,
 }
 
 impl Arc {
     pub fn new(start:Cart,end:Cart, radius:f64, large:bool,clockwise:bool, thickness:f64,) -> Self {
         Self {start, end, radius, large,clockwise, thickness}
     }
+    pub fn centre_and_angles(&self) -> (Cart,f64,f64,f64) {
+        //the centre, radius, start angle and sweep the svg would work out from the end points, angles are with y pointing down
+        let half = Cart::new((self.start.x-self.end.x)/2.0, (self.start.y-self.end.y)/2.0);
+        let half_sq = half.x*half.x + half.y*half.y;
+        if half_sq == 0.0 {
+            return (self.start, self.radius, 0.0, 0.0);
+        }
+        let radius = self.radius.max(half_sq.sqrt());//too small a radius gets scaled up, just like in svg
+        let mut coef = ((radius*radius - half_sq)/half_sq).max(0.0).sqrt();
+        if self.large == self.clockwise {
+            coef = -coef;
+        }
+        let offset = Cart::new(coef*half.y, -coef*half.x);
+        let centre = Cart::new(offset.x+(self.start.x+self.end.x)/2.0, offset.y+(self.start.y+self.end.y)/2.0);
+        let start = (self.start.y-centre.y).atan2(self.start.x-centre.x);
+        let end = (self.end.y-centre.y).atan2(self.end.x-centre.x);
+        let mut sweep = end-start;
+        if self.clockwise && sweep < 0.0 {
+            sweep += TAU;
+        } else if !self.clockwise && sweep > 0.0 {
+            sweep -= TAU;
+        }
+        return (centre, radius, start, sweep);
+    }
 }
 
 impl Shape for Arc {
@@ -208,35 +244,16 @@ impl Shape for Arc {
         self.start.shove(diff);
         self.end.shove(diff);
     }
-    fn to_element(&self) -> String {
-        let width = self.thickness;
-        let large = match self.large {
-            true => 1,
-            false => 0
-        };
-        let clockwise = match self.clockwise {
-            true => 1,
-            false => 0
-        };
-        return format!("<path fill-opacity=\"0\" stroke-width=\"{}\" d=\"M {} {} A {} {} 0 {} {} {} {}\" />",
-            self.thickness,
-            self.start.x,
-            self.start.y,
-            self.radius,
-            self.radius,
-            large,
-            clockwise,
-            self.end.x,
-            self.end.y,
-        )
+    fn draw(&self, renderer:&mut dyn Renderer) {
+        renderer.arc(self);
     }
 }
 pub
 struct Line {
-    start: Cart,
-    end: Cart,
-    thickness:f64,
-    rounded:bool,
+    pub start: Cart,
+    pub end: Cart,
+    pub thickness:f64,
+    pub rounded:bool,
 }
 impl Line {
     pub fn new(start:Cart, end:Cart, thickness:f64,rounded:bool) -> Self {
@@ -248,18 +265,29 @@ impl Shape for Line {
         self.start.shove(diff);
         self.end.shove(diff);
     }
-    fn to_element(&self) -> String {
-        let round = match self.rounded {
-            true => Circle::new(self.end,self.thickness*0.5,None).to_element(),
-            false => String::from(""),
-        };
-        return format!("<path stroke-width=\"{}\" d=\"M {} {} L {} {}\" />{}",
-                       self.thickness,
-                       self.start.x,
-                       self.start.y,
-                       self.end.x,
-                       self.end.y,
-            round
-        );
-    }
-}
\ No newline at end of file
+    fn draw(&self, renderer:&mut dyn Renderer) {
+        renderer.line(self);
+        if self.rounded {
+            renderer.circle(&Circle::new(self.end,self.thickness*0.5,None));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polar_round_trip() {
+        let tests = vec![(72, -56), (-57, 46), (-16, 23), (44, -26), (-61, 84), (-17, -79), (81, 2), (-5, 15), (86, 96), (-98, 75),
+                         (3,-4),(-3,-4),(-3,4),(3,4),
+                         (1,0),(0,-1),(-1,0),(0,1)];
+        for test in tests {
+            let (x,y) = test;
+            let mid = Polar::from(Cart::new(x as f64,y as f64));
+            let result = Cart::from(mid);
+            let result:(i32,i32) = (result.x.round() as i32,result.y.round() as i32);
+            assert_eq!(test, result, "{:?} went to {:?} via {:?}°", test, result, mid.theta*180f64/PI);
+        }
+    }
+}
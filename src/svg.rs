@@ -0,0 +1,111 @@
+use crate::shape::{Arc, Circle, Line, Renderer, ShapeSet};
+use crate::tree::Document;
+use crate::alphabet::Alphabet;
+use crate::options::RenderOptions;
+
+pub const NAMESPACE:&str = "https://github.com/D-G-Tomlinson/Gallifreyan";
+
+pub struct SvgWriter {
+    groups: Vec<Vec<String>>,//the elements of each group that is still open, the outermost first
+    accessible: bool,
+    titles: bool,
+}
+
+impl SvgWriter {
+    pub fn new(opts:&RenderOptions) -> Self {
+        Self { groups: vec![Vec::new()], accessible: opts.accessible, titles: opts.titles }
+    }
+    fn push(&mut self, element:String) {
+        if let Some(group) = self.groups.last_mut() {
+            group.push(element);
+        }
+    }
+    pub fn finish(mut self) -> String {
+        let els = self.groups.drain(..).flatten().collect::<Vec<_>>();
+        return els.join("\n");
+    }
+}
+
+impl Renderer for SvgWriter {
+    fn circle(&mut self, circle:&Circle) {
+        let (opacity,width) = match &circle.thickness {
+            Some(t) => (0.0,*t),
+            None => (1.0,0.0)
+        };
+        self.push(format!("<circle  cx=\"{}\" cy=\"{}\" r=\"{}\" stroke-width=\"{}\" fill-opacity=\"{}\" />",
+                          circle.centre.x,
+                          circle.centre.y,
+                          circle.radius,
+                          width,
+                          opacity
+        ));
+    }
+    fn arc(&mut self, arc:&Arc) {
+        let large = match arc.large {
+            true => 1,
+            false => 0
+        };
+        let clockwise = match arc.clockwise {
+            true => 1,
+            false => 0
+        };
+        self.push(format!("<path fill-opacity=\"0\" stroke-width=\"{}\" d=\"M {} {} A {} {} 0 {} {} {} {}\" />",
+                          arc.thickness,
+                          arc.start.x,
+                          arc.start.y,
+                          arc.radius,
+                          arc.radius,
+                          large,
+                          clockwise,
+                          arc.end.x,
+                          arc.end.y,
+        ));
+    }
+    fn line(&mut self, line:&Line) {
+        self.push(format!("<path stroke-width=\"{}\" d=\"M {} {} L {} {}\" />",
+                          line.thickness,
+                          line.start.x,
+                          line.start.y,
+                          line.end.x,
+                          line.end.y,
+        ));
+    }
+    fn begin_group(&mut self, _group:&ShapeSet) {
+        self.groups.push(Vec::new());
+    }
+    fn end_group(&mut self, group:&ShapeSet) {
+        let els = self.groups.pop().unwrap_or_default().join("\n");
+        let mut attributes = format!("class=\"{}\"", group.class());
+        let mut title = String::new();
+        if let Some(label) = group.label() {
+            let label = escape(label);
+            if self.accessible {
+                attributes += &format!(" role=\"{}\" aria-label=\"{label}\"", group.role());
+            }
+            if self.titles {
+                title = format!("<title>{label}</title>");
+            }
+        }
+        self.push(format!("<g {attributes}>{title}{els}</g>"));
+    }
+}
+
+pub fn escape(text:&str) -> String {//safe inside both elements and quoted attributes
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub fn metadata(text:&str, document:&Document, alphabet:&Alphabet) -> String {//what was written, so the svg can be searched, read aloud and made again
+    let title = escape(text.trim());
+    let text = escape(text).replace('\r', "&#13;");//exactly as written, even the line endings, which xml would otherwise tidy up
+    let letters = escape(&document.letter_sequence(alphabet));
+    let version = env!("CARGO_PKG_VERSION");
+    format!("    <title>{title}</title>
+    <desc>Sherman's Circular Gallifreyan, letter by letter: {letters}</desc>
+    <metadata>
+      <gallifreyan:source xmlns:gallifreyan=\"{NAMESPACE}\" version=\"{version}\">
+        <gallifreyan:text>{text}</gallifreyan:text>
+        <gallifreyan:letters>{letters}</gallifreyan:letters>
+      </gallifreyan:source>
+    </metadata>
+")
+}
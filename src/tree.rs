@@ -1,28 +1,29 @@
 use std::convert::TryFrom;
-use std::str::Chars;
-use std::collections::HashMap;
+use std::fmt;
 
 
 
-use crate::conversion::Svg;
-use crate::tree::Arc::{Above, Big, Small, On};
+use crate::alphabet::Alphabet;
+use crate::options::{ParseOptions, Unknown};
+use crate::respell::respell_word;
+use crate::lexer::{lex, to_mark, Token, TokenKind};
+use crate::error::{GallifreyanError, Offset, Warning};
 use crate::tree::Letter::{COpt, VOpt};
-use crate::tree::Marks::{Dot,Line};
-use crate::tree::Vowels::{A,E,I,O,U};
+use crate::tree::Vowels::{I,O};
 
-#[derive(Debug,Copy, Clone)]
+#[derive(Debug,Copy,Clone,PartialEq)]
 pub enum Arc{
     Big,
     Above,
     Small,
     On
 }
-#[derive(Debug,Copy, Clone)]
+#[derive(Debug,Copy,Clone,PartialEq)]
 pub enum Marks {
     Dot(i32),
     Line(i32),
 }
-#[derive(Debug,Copy, Clone)]
+#[derive(Debug,Copy,Clone,PartialEq)]
 pub enum Vowels {
     A,
     E,
@@ -38,99 +39,75 @@ impl Vowels {
         }
     }
 }
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq)]
 pub struct Vowel {
     pub v:Vowels,
     pub double:bool,
 }
-fn get_v(letter:Vowels,double:bool) -> Letter {
+pub(crate) fn get_v(letter:Vowels,double:bool) -> Letter {
     return Letter::VOpt(Vowel{v:letter, double});
 }
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq)]
 pub struct Consonant {
     pub arc: Arc,
     pub marks: Option<Marks>,
     pub diacritic: Option<Vowel>,
 }
-fn get_c(arc: Arc,marks: Option<Marks>) -> Letter {
+pub(crate) fn get_c(arc: Arc,marks: Option<Marks>) -> Letter {
     let diacritic = None;
     return COpt(Consonant {arc,marks,diacritic});
 }
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq)]
 pub enum Letter {
     COpt(Consonant),
     VOpt(Vowel),
+    Apostrophe,//a break in the word circle, as in don't
 }
 
-fn chars_to_letters(chars:Vec<char>) -> Result<Vec<Letter>,String> {
-    let singles:HashMap<char,Letter> = HashMap::from([
-        ('a',get_v(A,false)),
-        ('e',get_v(E,false)),
-        ('i',get_v(I,false)),
-        ('o',get_v(O,false)),
-        ('u',get_v(U,false)),
-        ('b',get_c(Big,None)),
-        ('d',get_c(Big,Some(Dot(3)))),
-        ('g',get_c(Big,Some(Line(1)))),
-        ('h',get_c(Big,Some(Line(2)))),
-        ('f',get_c(Big,Some(Line(3)))),
-        ('j',get_c(Above,None)),
-        ('k',get_c(Above,Some(Dot(2)))),
-        ('l',get_c(Above,Some(Dot(3)))),
-        ('c',get_c(Above,Some(Dot(4)))),
-        ('n',get_c(Above,Some(Line(1)))),
-        ('p',get_c(Above,Some(Line(2)))),
-        ('m',get_c(Above,Some(Line(3)))),
-        ('t',get_c(Small,None)),
-        ('r',get_c(Small,Some(Dot(3)))),
-        ('v',get_c(Small,Some(Line(1)))),
-        ('w',get_c(Small,Some(Line(2)))),
-        ('s',get_c(Small,Some(Line(3)))),
-        ('y',get_c(On,Some(Dot(2)))),
-        ('z',get_c(On,Some(Dot(3)))),
-        ('q',get_c(On,Some(Dot(4)))),
-        ('x',get_c(On,Some(Line(2)))),
-    ]);
-    let doubles:HashMap<(char,char),Letter> = HashMap::from([
-        (('c','h'),get_c(Big,Some(Dot(2)))),
-        (('n','d'),get_c(Big,Some(Dot(4)))),
-        (('p','h'),get_c(Above,Some(Dot(1)))),
-        (('w','h'),get_c(Small,Some(Dot(1)))),
-        (('s','h'),get_c(Small,Some(Dot(2)))),
-        (('n','t'),get_c(Small,Some(Dot(4)))),
-        (('t','h'),get_c(On,None)),
-        (('g','h'),get_c(On,Some(Dot(1)))),
-        (('q','u'),get_c(On,Some(Line(1)))),
-        (('n','g'),get_c(On,Some(Line(3)))),
-        (('a','a'),get_v(Vowels::A,true)),
-        (('e','e'),get_v(Vowels::E,true)),
-        (('i','i'),get_v(Vowels::I,true)),
-        (('o','o'),get_v(Vowels::O,true)),
-        (('u','u'),get_v(Vowels::U,true)),
-    ]);
+fn chars_to_letters(chars:Vec<char>, alphabet:&Alphabet, options:&ParseOptions, warnings:&mut Vec<Warning>) -> Result<Vec<Letter>,GallifreyanError> {
     let mut result:Vec<Letter> = Vec::new();
     let mut i = 0;
-    while i < chars.len()-1 {
+    while i < chars.len() {
         let next = chars[i];
-        let next_two :(char,char) = (chars[i],chars[i+1]);
-        if doubles.contains_key(&next_two) {
-            result.push(doubles[&next_two].clone());
-            i += 2;
-        } else if singles.contains_key(&next) {
-            result.push(singles[&next].clone());
+        if next == '\'' {//the same in every alphabet
+            result.push(Letter::Apostrophe);
             i += 1;
-        } else {
-            return Err(format!("Invalid letter found: {:#?}",next));
+            continue;
         }
-    }
-    if i<chars.len() && singles.contains_key(&chars[i]) {
-        result.push(singles[&chars[i]].clone());
+        if let Some(after) = chars.get(i+1) && let Some(l) = alphabet.double(next, *after) {
+            result.push(l.clone());
+            i += 2;
+            continue;
+        }
+        let offset = Offset::at(&chars,i);
+        match (alphabet.single(next), options.unknown) {
+            (Some(l), _) => result.push(l.clone()),
+            (None, Unknown::Error) => return Err(GallifreyanError::InvalidLetter{character:next, offset}),
+            (None, Unknown::Replace) if alphabet.single(options.replacement).is_some() => {
+                result.extend(alphabet.single(options.replacement).cloned());
+                warnings.push(Warning{character:next, offset, replacement:Some(options.replacement)});
+            },
+            (None, _) => warnings.push(Warning{character:next, offset, replacement:None}),
+        }
+        i += 1;
     }
     return Ok(result);
 }
 
+impl Letter {
+    pub fn text(&self, alphabet:&Alphabet) -> Option<String> {//the spelling that gives this letter in the alphabet, none if nothing does
+        match self {
+            Letter::Apostrophe => Some("'".to_string()),
+            _ => alphabet.spell(self),
+        }
+    }
+}
+
 fn join_cv(letters:Vec<Letter>) -> Vec<Letter> {
     let mut result:Vec<Letter> = Vec::new();
+    if letters.is_empty() {
+        return result;
+    }
 
     let mut i = 0;
 
@@ -162,95 +139,271 @@ fn join_cv(letters:Vec<Letter>) -> Vec<Letter> {
 }
 
 impl TryFrom<Vec<char>> for Word {
-    type Error = String;
+    type Error = GallifreyanError;
     fn try_from(chars:Vec<char>) -> Result<Self, Self::Error> {
-        let result = match chars_to_letters(chars) {
-            Ok(l) => l,
-            Err(e) => return Err(e)
-        };
-        let result=join_cv(result);
-        return Ok(Word(result));
+        Self::try_from((chars,Alphabet::sherman()))
     }
 }
 
-#[derive(Debug,Clone)]
+impl TryFrom<(Vec<char>,&Alphabet)> for Word {
+    type Error = GallifreyanError;
+    fn try_from((chars,alphabet):(Vec<char>,&Alphabet)) -> Result<Self, Self::Error> {
+        read_word(chars, alphabet, &ParseOptions::default(), &mut Vec::new())
+    }
+}
+
+fn read_word(chars:Vec<char>, alphabet:&Alphabet, options:&ParseOptions, warnings:&mut Vec<Warning>) -> Result<Word,GallifreyanError> {
+    let result = chars_to_letters(chars, alphabet, options, warnings)?;
+    return Ok(Word(join_cv(result)));
+}
+
+#[derive(Debug,Clone,PartialEq)]
 pub struct Word(Vec<Letter>);
 
 impl Word {
     pub fn word(&self) -> &Vec<Letter> {
         &self.0
     }
-}
-
-impl Word {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub(crate) fn joined(letters:Vec<Letter>) -> Self {//consonants take the vowel after them, as in a spelt word
+        Word(join_cv(letters))
+    }
+    fn spell(&self, between:&str, alphabet:&Alphabet) -> String {//between goes between the letters
+        self.0.iter().map(|l| l.text(alphabet).unwrap_or_default()).collect::<Vec<_>>().join(between)
+    }
+    pub fn text(&self, alphabet:&Alphabet) -> String {//spelt with the letters of that alphabet, display uses Sherman's
+        self.spell("", alphabet)
+    }
     pub fn get_num_things(&self) -> u32 {
         let mut i = 0;
         for thing in &self.0 {
             match thing {
                 COpt(_) => i += 1,
                 VOpt(_) => i += 1,
-            }//this will be important for apostrophes
+                Letter::Apostrophe => (),//drawn in the gap between two letters, so takes no room of its own
+            }
         }
         return i;
     }
 }
 
-#[derive(Debug,Clone)]
+impl From<Vec<Letter>> for Word {
+    fn from(letters:Vec<Letter>) -> Self {
+        Word(letters)
+    }
+}
+
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text(Alphabet::sherman()))
+    }
+}
+
+#[derive(Debug,Clone,PartialEq)]
 pub struct Digit {
-    pub has_circle: bool,
+    pub num_circles: u8,//each worth five, so up to 15 can be drawn for hexadecimal
     pub num_lines:u8,
     pub follows_dot: bool,
+    pub follows_comma: bool,//a thousands separator comes before it
 }
 
 impl Digit {
-    fn try_from(char:&char,follows_dot:bool) -> Result<Self, String> {
-        if !('0'..='9').contains(&char) {
-            return Err(format!("invalid digit {:#?}", char));
-        }
-        let val = char.to_digit(10).unwrap() as u8;
-        let has_circle = val >= 5;
+    fn try_from(char:&char,base:u8,follows_dot:bool) -> Result<Self, GallifreyanError> {
+        let Some(val) = char.to_digit(base as u32) else {
+            return Err(GallifreyanError::InvalidDigit{character:*char, offset:Offset::default()});
+        };
+        let val = val as u8;
+        let num_circles = val / 5;
         let num_lines = val % 5;
-        return Ok(Digit{has_circle,num_lines,follows_dot});
+        return Ok(Digit{num_circles,num_lines,follows_dot,follows_comma:false});
+    }
+    pub fn value(&self) -> u8 {
+        self.num_circles*5 + self.num_lines
+    }
+    pub fn text(&self) -> char {//0 to 9, then a to f
+        std::char::from_digit(self.value() as u32, 16).unwrap_or('?')
     }
 }
 
-#[derive(Debug,Clone)]
+pub(crate) fn prefix(base:u8) -> &'static str {//how a number in this base is written, none are written for bases without one
+    match base {
+        2 => "0b",
+        8 => "0o",
+        16 => "0x",
+        _ => "",
+    }
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub enum Suffix {//what comes after the digits of a number
+    Fraction(Number),//over this, as in 3/4
+    Exponent(Number),//times ten to the power of this, as in 6.02e23
+    Percent,
+}
+
+#[derive(Debug,Clone,PartialEq)]
 pub struct Number {
     pub is_positive: bool,
     pub is_whole:bool,
+    pub(crate) base: u8,//2, 8 or 16 if it was written with 0b, 0o or 0x, 10 otherwise, set through in_base so it can always be written down
     pub digits: Vec<Digit>,
+    pub suffix: Option<Box<Suffix>>,
+}
+
+impl Number {
+    pub fn base(&self) -> u8 {
+        self.base
+    }
+    pub fn in_base(mut self, base:u8) -> Result<Self,GallifreyanError> {//the same digits in another base, only ones with a prefix can be written back down
+        if base != 10 && prefix(base).is_empty() {
+            return Err(GallifreyanError::InvalidNumber{reason:format!("base {base} can't be written, it must be 2, 8, 10 or 16"), offset:Offset::default()});
+        }
+        if let Some(d) = self.digits.iter().find(|d| d.value() >= base) {
+            return Err(GallifreyanError::InvalidDigit{character:d.text(), offset:Offset::default()});
+        }
+        self.base = base;
+        return Ok(self);
+    }
+}
+
+fn invalid_number(reason:&str, chars:&[char], i:usize) -> GallifreyanError {
+    GallifreyanError::InvalidNumber{reason:reason.to_string(), offset:Offset::at(chars,i)}
 }
+
+fn check_groups(chars:&[char], start:usize) -> Result<(),GallifreyanError> {//thousands separators only go every three digits before the decimal point
+    let point = chars.iter().position(|c| *c == '.').unwrap_or(chars.len());
+    if let Some(i) = chars[point..].iter().position(|c| *c == ',') {
+        return Err(invalid_number("thousands separators can't come after the decimal point", chars, point+i));
+    }
+    let groups:Vec<&[char]> = chars[start..point].split(|c| *c == ',').collect();
+    if groups.len() > 1 && (!(1..=3).contains(&groups[0].len()) || groups[1..].iter().any(|g| g.len() != 3)) {
+        return Err(invalid_number("digits between thousands separators must come in threes", chars, start));
+    }
+    return Ok(());
+}
+
+fn base(chars:&[char]) -> u8 {//from the prefix after any sign
+    let sign = if chars.first() == Some(&'-') {1} else {0};
+    match chars.get(sign..sign+2) {
+        Some(['0','b']) => 2,
+        Some(['0','o']) => 8,
+        Some(['0','x']) => 16,
+        _ => 10,
+    }
+}
+
+fn read_digits(chars:&[char]) -> Result<Number,GallifreyanError> {//a signed number, with or without a base prefix and thousands separators
+    if chars.len() == 0 {
+        return Err(GallifreyanError::EmptyNumber{offset:Offset::default()});
+    }
+    let is_positive = chars[0] != '-';
+    let base = base(chars);
+    let start = (if !is_positive {1} else {0}) + prefix(base).len();
+    check_groups(chars, start)?;
+    let mut digits:Vec<Digit> = Vec::new();
+    let mut was_dot = false;
+    let mut was_comma = false;
+    let mut is_whole = true;
+    for (i,d) in chars.iter().enumerate().skip(start) {
+        if *d =='.' {
+            if !is_whole {
+                return Err(invalid_number("it has more than one decimal point", chars, i));
+            }
+            if i+1 == chars.len() {
+                return Err(invalid_number("there are no digits after the decimal point", chars, i));
+            }
+            if digits.is_empty() {//.5 is drawn as 0.5, so the first ring is never the thick one after a point
+                digits.push(Digit::try_from(&'0',base,false)?);
+            }
+            was_dot = true;
+            is_whole = false;
+        } else if *d == ',' {
+            was_comma = true;
+        } else {
+            let mut digit = Digit::try_from(d,base,was_dot).map_err(|e| e.shift(Offset::at(chars,i)))?;
+            digit.follows_comma = was_comma;
+            digits.push(digit);
+            was_dot = false;
+            was_comma = false;
+        }
+    }
+    if digits.is_empty() {
+        return Err(GallifreyanError::EmptyNumber{offset:Offset::default()});
+    }
+    Ok(Number{ is_positive,is_whole, base, digits, suffix:None})
+}
+
 impl TryFrom<Vec<char>> for Number {
-    type Error = String;
+    type Error = GallifreyanError;
     fn try_from(chars:Vec<char>) -> Result<Self, Self::Error> {
-        if chars.len() == 0 {
-            return Err("empty number".to_string());
+        let sign = if chars.first() == Some(&'-') {1} else {0};
+        let notation = if base(&chars) == 16 {"/%-"} else {"/%-e"};//e is a digit in hexadecimal, so only the minus after it gives it away
+        if base(&chars) != 10 && let Some(i) = chars.iter().skip(sign).position(|c| notation.contains(*c)) {//the lexer reads these in after any number, so they can be reported here
+            return Err(invalid_number("only decimal numbers can be fractions, percentages or powers of ten", &chars, sign+i));
         }
-        let is_positive = chars[0] != '-';
-        let start = if !is_positive {1} else {0};
-        let mut digits:Vec<Digit> = Vec::new();
-        let mut was_dot = false;
-        let mut is_whole = true;
-        for d in &chars[start..] {
-            if *d =='.' {
-                was_dot = true;
-                is_whole = false;
-            } else {
-                digits.push(Digit::try_from(d,was_dot)?);
-                was_dot = false;
-            }
+        let split = chars.iter().position(|c| "/e%".contains(*c)).filter(|_| base(&chars) == 10);//e is a digit in hexadecimal
+        let Some(i) = split else {
+            return read_digits(&chars);
+        };
+        let mut number = read_digits(&chars[..i])?;
+        let rest = || Number::try_from(chars[i+1..].to_vec()).map_err(|e| e.shift(Offset::at(&chars,i+1)));
+        let suffix = match chars[i] {
+            '/' => {
+                let under = rest()?;
+                if !number.is_whole || !under.is_whole || under.suffix.is_some() {
+                    return Err(invalid_number("both sides of a fraction must be whole numbers", &chars, i));
+                }
+                if !under.is_positive || under.digits.iter().all(|d| d.value() == 0) {
+                    return Err(invalid_number("a fraction must be over a number above zero", &chars, i));
+                }
+                Suffix::Fraction(under)
+            },
+            'e' => {
+                let power = rest()?;
+                if !power.is_whole || power.suffix.is_some() {
+                    return Err(invalid_number("an exponent must be a whole number", &chars, i));
+                }
+                Suffix::Exponent(power)
+            },
+            _ => {
+                if i+1 < chars.len() {
+                    return Err(invalid_number("nothing can come after a percent sign", &chars, i+1));
+                }
+                Suffix::Percent
+            },
+        };
+        number.suffix = Some(Box::new(suffix));
+        return Ok(number);
+    }
+}
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_positive {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", prefix(self.base))?;
+        for d in &self.digits {
+            let separator = if d.follows_dot {"."} else if d.follows_comma {","} else {""};
+            write!(f, "{}{}", separator, d.text())?;
+        }
+        match self.suffix.as_deref() {
+            Some(Suffix::Fraction(under)) => write!(f, "/{under}"),
+            Some(Suffix::Exponent(power)) => write!(f, "e{power}"),
+            Some(Suffix::Percent) => write!(f, "%"),
+            None => Ok(()),
         }
-        Ok(Number{ is_positive,is_whole, digits})
     }
 }
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq)]
 pub enum WordTypes {
-    PlainWord(Vec<char>),
+    PlainWord(Word),
     Punctuation(Vec<PunctuationTypes>),
-    Number(Vec<char>),
+    Number(Number),
 }
 use crate::tree::PunctuationTypes::*;
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq)]
 pub enum PunctuationTypes {
     NEnd(char),//not sentence ender
     SEnd(char),//sentence ender
@@ -259,7 +412,7 @@ pub enum PunctuationTypes {
 use crate::tree::WordTypes::*;
 
 
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq)]
 pub struct Sentence {
     pub words: Vec<WordTypes>
 }
@@ -276,117 +429,340 @@ impl Sentence {
         }
         return num_words;
     }
-}
-impl TryFrom<Vec<char>> for Sentence {
-    type Error = String;
-    fn try_from(input:Vec<char>) -> Result<Sentence,String> {
+    fn ends_sentence(word:&WordTypes) -> bool {
+        match word {
+            Punctuation(marks) => marks.iter().any(|m| matches!(m, SEnd(_))),
+            _ => false
+        }
+    }
+    pub fn split(self) -> Vec<Sentence> {//break at sentence ending punctuation, which stays with the sentence it ends
+        let mut sentences:Vec<Sentence> = Vec::new();
         let mut words:Vec<WordTypes> = Vec::new();
-        let mut current_word = None;
-        for i in 0..input.len() {
-            let c = &input[i];
-            match &c {
-                '0'..='9' => {
-                    if let Some(Number(ref mut word)) = current_word {
-                        word.push(*c);
-                    } else {
-                        if let Some(cw) = current_word {
-                            words.push(cw);
-                        }
-                        current_word = Some(Number(vec![*c]));
-                    }
-                },
-                '.' => {
-                    if let Some(Number(ref mut word)) = current_word {
-                        if let Some(next) = input.get(i+1) {
-                            if ('0'..='9').contains(&next)  {
-                                word.push(*c);
-                            }
-                        }
-                    } else if let Some(Punctuation(ref mut word)) = current_word {
-                        word.push(SEnd(c.clone()));
-                    }  else {
-                        if let Some(cw) = current_word {
-                            words.push(cw);
-                        }
-                        current_word = Some(Punctuation(vec![SEnd(c.clone())]));
-                    }
-                },
-                '-' => {
-                    if let Some(next) = input.get(i+1) && ('0'..='9').contains(&next) {
-                        if let Some(cw) = current_word {
-                            words.push(cw);
-                        }
-                        current_word = Some(Number(vec![*c]));
-                    }
-                    else if let Some(Punctuation(ref mut word)) = current_word {
-                        word.push(NEnd(c.clone()));
-                    }  else {
-                        if let Some(cw) = current_word {
-                            words.push(cw);
-                        }
-                        current_word = Some(Punctuation(vec![NEnd(c.clone())]));
-                    }
-                },
-                'a'..='z' => {
-                    if let Some(PlainWord(ref mut word)) = current_word {
-                        word.push(*c);
-                    } else {
-                        if let Some(cw) = current_word {
-                            words.push(cw);
-                        }
-                        current_word = Some(PlainWord(vec![*c]));
-                    }
-                },
-                '\'' => {
-                    if let Some(PlainWord(ref mut word)) = current_word {
-                        if let Some(next) = input.get(i+1) && ('a'..='z').contains(&next) {
-                            word.push(*c);
-                        }
-                    } else if let Some(Punctuation(ref mut word)) = current_word {
-                        word.push(NEnd(c.clone()));
-                    } else {
-                        if let Some(cw) = current_word {
-                            words.push(cw);
-                        }
-                        current_word = Some(Punctuation(vec![NEnd(c.clone())]));
-                    }
-                },
-                ' ' => {
-                    if let Some(Punctuation(_)) = current_word {
-                        ()
-                    } else if let Some(ref cw) = current_word {
-                        words.push(cw.clone());
-                        current_word = None;
-                    }
-                },
-                //normal, non ending punctuation
-                '"'|','|';'|':' => {
-                    if let Some(Punctuation(ref mut word)) = current_word {
-                        word.push(NEnd(c.clone()));
-                    } else {
-                        if let Some(cw) = current_word {
-                            words.push(cw);
-                        }
-                        current_word = Some(PlainWord(vec![*c]));
+        for w in self.words {
+            let ends = Self::ends_sentence(&w);
+            words.push(w);
+            if ends {
+                sentences.push(Sentence{words});
+                words = Vec::new();
+            }
+        }
+        if !words.is_empty() {
+            sentences.push(Sentence{words});
+        }
+        return sentences;
+    }
+    fn spell(&self, between:&str, alphabet:&Alphabet) -> String {//punctuation sits against the word before it, unless it could be read back as part of that word
+        let mut result = String::new();
+        for (i,w) in self.words.iter().enumerate() {
+            let space = if i == 0 {""} else {" "};
+            match w {
+                PlainWord(word) => result += &format!("{space}{}", word.spell(between, alphabet)),
+                Number(number) => result += &format!("{space}{number}"),
+                Punctuation(marks) => for (j,m) in marks.iter().enumerate() {
+                    if j == 0 && i > 0 && matches!(m, NEnd('\'')) {
+                        result.push(' ');
                     }
-                },
-                //normal, ending punctuation
-                '?'|'!' => {
-                    if let Some(Punctuation(ref mut word)) = current_word {
-                        word.push(SEnd(c.clone()));
-                    } else {
-                        if let Some(cw) = current_word {
-                            words.push(cw);
-                        }
-                        current_word = Some(PlainWord(vec![*c]));
+                    match m {
+                        NEnd(c)|SEnd(c) => result.push(*c),
                     }
                 },
-                _ => return Err(format!("{:#?} is not a valid letter", c)),
             }
         }
-        if let Some(cw) = current_word {
-            words.push(cw);
+        return result;
+    }
+    pub fn text(&self, alphabet:&Alphabet) -> String {
+        self.spell("", alphabet)
+    }
+}
+
+impl fmt::Display for Sentence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text(Alphabet::sherman()))
+    }
+}
+fn letters(chars:Vec<char>, alphabet:&Alphabet, options:&ParseOptions, warnings:&mut Vec<Warning>) -> Result<Word,GallifreyanError> {
+    if !options.respell {
+        return read_word(chars, alphabet, options, warnings);
+    }
+    let (respelt,origins) = respell_word(&chars);
+    let origins:Vec<Offset> = origins.iter().map(|o| Offset::at(&chars,*o)).collect();
+    let mut found = Vec::new();
+    let word = read_word(respelt, alphabet, options, &mut found).map_err(|e| e.locate(&origins))?;
+    warnings.extend(found.into_iter().map(|w| w.locate(&origins)));
+    return Ok(word);
+}
+
+fn push_word(words:&mut Vec<WordTypes>, token:Token, start:Offset, alphabet:&Alphabet, options:&ParseOptions, warnings:&mut Vec<Warning>) -> Result<(),GallifreyanError> {//parse the finished token, so errors know where they are
+    let word = match token.kind {
+        TokenKind::Letters => {
+            let mut found = Vec::new();
+            let word = letters(token.chars, alphabet, options, &mut found).map_err(|e| e.shift(start))?;
+            warnings.extend(found.into_iter().map(|w| w.shift(start)));
+            if word.is_empty() {//every letter was skipped
+                return Ok(());
+            }
+            PlainWord(word)
+        },
+        TokenKind::Digits => Number(crate::tree::Number::try_from(token.chars).map_err(|e| e.shift(start))?),
+        TokenKind::Marks => Punctuation(token.chars.into_iter().map(to_mark).collect()),
+        TokenKind::Break => return Ok(()),//paragraphs are split up before their words are read
+    };
+    words.push(word);
+    return Ok(());
+}
+
+impl TryFrom<Vec<char>> for Sentence {
+    type Error = GallifreyanError;
+    fn try_from(input:Vec<char>) -> Result<Sentence,GallifreyanError> {
+        Self::try_from((input,Alphabet::sherman(),&ParseOptions::default()))
+    }
+}
+
+impl TryFrom<(Vec<char>,&Alphabet,&ParseOptions)> for Sentence {
+    type Error = GallifreyanError;
+    fn try_from((input,alphabet,options):(Vec<char>,&Alphabet,&ParseOptions)) -> Result<Sentence,GallifreyanError> {
+        read_sentence(input, alphabet, options, &mut Vec::new())
+    }
+}
+
+fn read_paragraphs(input:Vec<char>, alphabet:&Alphabet, options:&ParseOptions, warnings:&mut Vec<Warning>) -> Result<Vec<Vec<WordTypes>>,GallifreyanError> {//the words of each paragraph
+    let mut paragraphs:Vec<Vec<WordTypes>> = vec![Vec::new()];
+    for token in lex(&input, options, warnings)? {
+        if token.kind == TokenKind::Break {
+            paragraphs.push(Vec::new());
+            continue;
         }
-        return Ok(Self{words});
+        let start = Offset::at(&input,token.start);
+        let words = paragraphs.last_mut().expect("there is always a paragraph");
+        push_word(words, token, start, alphabet, options, warnings)?;
+    }
+    return Ok(paragraphs);
+}
+
+fn read_sentence(input:Vec<char>, alphabet:&Alphabet, options:&ParseOptions, warnings:&mut Vec<Warning>) -> Result<Sentence,GallifreyanError> {//blank lines are just whitespace here
+    let words = read_paragraphs(input, alphabet, options, warnings)?.concat();
+    return Ok(Sentence{words});
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Paragraph {
+    pub sentences: Vec<Sentence>
+}
+
+impl Paragraph {
+    fn spell(&self, between:&str, alphabet:&Alphabet) -> String {//sentences are separated by a space
+        self.sentences.iter().map(|s| s.spell(between, alphabet)).collect::<Vec<_>>().join(" ")
+    }
+    pub fn text(&self, alphabet:&Alphabet) -> String {
+        self.spell("", alphabet)
+    }
+}
+
+impl fmt::Display for Paragraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text(Alphabet::sherman()))
+    }
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Document {
+    pub paragraphs: Vec<Paragraph>
+}
+
+impl Document {
+    pub fn sentences(&self) -> impl Iterator<Item=&Sentence> {
+        self.paragraphs.iter().flat_map(|p| p.sentences.iter())
     }
-}
\ No newline at end of file
+    fn spell(&self, between:&str, alphabet:&Alphabet) -> String {//paragraphs are separated by a blank line
+        let paragraphs:Vec<String> = self.paragraphs.iter().map(|p| p.spell(between, alphabet)).collect();
+        return paragraphs.join("\n\n");
+    }
+    pub fn text(&self, alphabet:&Alphabet) -> String {//spelt with the letters of the alphabet it was read with
+        self.spell("", alphabet)
+    }
+    pub fn letter_sequence(&self, alphabet:&Alphabet) -> String {//the text as it is drawn, with a dot between the letters of each word
+        self.spell("·", alphabet)
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text(Alphabet::sherman()))
+    }
+}
+
+impl From<Sentence> for Document {
+    fn from(sentence:Sentence) -> Self {
+        Self{paragraphs: vec![Paragraph{sentences: vec![sentence]}]}
+    }
+}
+
+impl TryFrom<Vec<char>> for Document {
+    type Error = GallifreyanError;
+    fn try_from(input:Vec<char>) -> Result<Document,GallifreyanError> {
+        Self::try_from((input,Alphabet::sherman(),&ParseOptions::default()))
+    }
+}
+
+impl TryFrom<(Vec<char>,&Alphabet,&ParseOptions)> for Document {
+    type Error = GallifreyanError;
+    fn try_from((input,alphabet,options):(Vec<char>,&Alphabet,&ParseOptions)) -> Result<Document,GallifreyanError> {
+        read_document(input, alphabet, options, &mut Vec::new())
+    }
+}
+
+pub(crate) fn read_document(input:Vec<char>, alphabet:&Alphabet, options:&ParseOptions, warnings:&mut Vec<Warning>) -> Result<Document,GallifreyanError> {//warnings collects whatever was skipped or replaced
+    let mut paragraphs:Vec<Paragraph> = read_paragraphs(input, alphabet, options, warnings)?.into_iter()
+        .map(|words| Paragraph{sentences: Sentence{words}.split()})
+        .filter(|p| !p.sentences.is_empty())//every word in it was skipped
+        .collect();
+    if paragraphs.is_empty() {
+        paragraphs.push(Paragraph{sentences: Vec::new()});
+    }
+    return Ok(Document{paragraphs});
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversion::{parse, parse_with_warnings};
+
+    fn lenient(text:&str, unknown:Unknown, alphabet:&Alphabet) -> (Document,Vec<Warning>) {
+        let options = ParseOptions{unknown, replacement:'b', ..ParseOptions::default()};
+        parse_with_warnings(text, alphabet, &options).unwrap()
+    }
+
+    #[test]
+    fn unknown_characters() {
+        let sherman = Alphabet::sherman();
+        let (document,warnings) = lenient("He@llo", Unknown::Skip, sherman);
+        assert_eq!(document, parse("hello").unwrap());
+        assert_eq!(warnings, vec![Warning{character:'@', offset:Offset::new(2,2), replacement:None}]);
+
+        let (document,warnings) = lenient("rock&roll", Unknown::Replace, sherman);
+        assert_eq!(document, parse("rockbroll").unwrap());
+        assert_eq!(warnings[0].replacement, Some('b'));
+
+        let (document,warnings) = lenient("@ #", Unknown::Skip, sherman);
+        assert_eq!(document.sentences().count(), 0);
+        assert_eq!(warnings.len(), 2);
+
+        let no_z = Alphabet::from_toml("[letters]\na = { vowel = \"a\" }\nb = { arc = \"big\" }").unwrap();
+        let (document,warnings) = lenient("baz zz", Unknown::Skip, &no_z);
+        assert_eq!(document, parse("ba").unwrap());
+        assert_eq!(warnings.iter().map(|w| w.offset.char).collect::<Vec<_>>(), vec![2,4,5]);
+        let (document,_) = lenient("baz", Unknown::Replace, &no_z);
+        assert_eq!(document, parse("bab").unwrap());
+
+        assert!(parse_with_warnings("he@llo", sherman, &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn punctuation_between_words() {
+        let document = parse("\"Hello,\" she said; \"why: now?\"\tOk.\nbye").unwrap();
+        let sentences:Vec<&Sentence> = document.sentences().collect();
+        assert_eq!(sentences.len(), 3);
+        assert_eq!(sentences[0].words[0], Punctuation(vec![NEnd('"')]));
+        assert_eq!(sentences[0].words[2], Punctuation(vec![NEnd(','), NEnd('"')]));
+        assert_eq!(sentences[0].get_num_words(), 5);
+        assert_eq!(sentences[2].to_string(), "bye");
+        assert_eq!(parse(" hi  there ").unwrap(), parse("hi there").unwrap());
+    }
+
+    #[test]
+    fn apostrophes() {
+        let document = parse("Don't, it's rock'n'roll").unwrap();
+        let words:Vec<&WordTypes> = document.sentences().flat_map(|s| s.words.iter()).collect();
+        let PlainWord(dont) = words[0] else { panic!("{:#?} is not a word", words[0]) };
+        assert_eq!(dont.word()[2], Letter::Apostrophe);
+        assert_eq!(dont.get_num_things(), 3);
+        assert_eq!(words[1], &Punctuation(vec![NEnd(',')]));
+        assert_eq!(document.to_string(), "don't, it's rock'n'roll");
+        assert_eq!(document.letter_sequence(Alphabet::sherman()), "do·n·'·t, i·t·'·s ro·c·k·'·n·'·ro·l·l");
+        assert_eq!(Word::try_from(vec!['l','\'','a']).unwrap().word().len(), 3);//the vowel isn't joined across the apostrophe
+    }
+
+    #[test]
+    fn paragraphs() {
+        let document = parse("One. Two\nthree!\n\n\tFour\n\n").unwrap();
+        assert_eq!(document.paragraphs.len(), 2);
+        assert_eq!(document.paragraphs[0].sentences.len(), 2);
+        assert_eq!(document.paragraphs[1].to_string(), "four");
+        assert_eq!(document.to_string(), "one. two three!\n\nfour");
+        assert_eq!(parse("a\n\nb\u{263A}"), Err(GallifreyanError::InvalidCharacter{character:'\u{263A}', offset:Offset::new(4,4)}));
+        assert_eq!(parse("").unwrap().paragraphs.len(), 1);
+        assert_eq!(Sentence::try_from("a\n\nb".chars().collect::<Vec<_>>()).unwrap().get_num_words(), 2);
+    }
+
+    #[test]
+    fn number_edges() {
+        assert_eq!(parse(".5").unwrap(), parse("0.5").unwrap());
+        assert_eq!(parse("-.25").unwrap().to_string(), "-0.25");
+        assert_eq!(parse("so .5, 1").unwrap().to_string(), "so 0.5, 1");
+
+        let error = parse("v 1.2.3").unwrap_err();
+        assert!(matches!(error, GallifreyanError::InvalidNumber{offset:Offset{char:5, ..}, ..}), "{:#?}", error);
+        assert!(matches!(Number::try_from(vec!['5','.']), Err(GallifreyanError::InvalidNumber{..})));
+        assert!(matches!(Number::try_from(vec!['-']), Err(GallifreyanError::EmptyNumber{..})));
+
+        let document = parse("Pi is 3.14. It costs 5! Or 6? 7, 8; 9: 10").unwrap();
+        let sentences:Vec<&Sentence> = document.sentences().collect();
+        assert_eq!(sentences.len(), 4);
+        assert_eq!(sentences[0].words[2..], [Number(crate::tree::Number::try_from(vec!['3','.','1','4']).unwrap()), Punctuation(vec![SEnd('.')])]);
+        assert_eq!(document.to_string(), "pi is 3.14. it costs 5! or 6? 7, 8; 9: 10");
+        assert_eq!(parse("end.5").unwrap().to_string(), "end. 5");
+        for (text,at) in [("1..5", 2), ("..5", 1), ("go to 1..5!", 8), ("wait..5", 5)] {
+            let error = parse(text).unwrap_err();
+            assert!(matches!(error, GallifreyanError::InvalidNumber{..}), "{:#?} gave {:#?}", text, error);
+            assert_eq!(error.offset().char, at, "{:#?} was reported in the wrong place", text);
+        }
+    }
+
+    #[test]
+    fn number_notation() {
+        let same = ["3/4", "-3/4", "6.02e23", "1e-5", "50%", "12.5%", "1,000", "-1,234,567.89", "pick 1, 2 or 3"];
+        for text in same {
+            assert_eq!(parse(text).unwrap().to_string(), text);
+        }
+        let number = crate::tree::Number::try_from("3/4".chars().collect::<Vec<_>>()).unwrap();
+        assert_eq!(number.digits.len(), 1);
+        let Some(Suffix::Fraction(under)) = number.suffix.as_deref() else { panic!("{:#?} is not a fraction", number) };
+        assert_eq!(under.digits[0].value(), 4);
+        let thousands = crate::tree::Number::try_from("12,345".chars().collect::<Vec<_>>()).unwrap();
+        assert_eq!(thousands.digits.iter().map(|d| d.follows_comma).collect::<Vec<_>>(), vec![false,false,true,false,false]);
+
+        let bad = [("1/0", 1), ("1.5/2", 3), ("1/-2", 1), ("1e2.5", 1), ("1e5%", 1), ("12,34", 0), ("1234,567", 0), ("1.000,000", 5), ("5%5", 2)];
+        for (text,at) in bad {
+            let error = crate::tree::Number::try_from(text.chars().collect::<Vec<_>>()).unwrap_err();
+            assert!(matches!(error, GallifreyanError::InvalidNumber{..}), "{:#?} gave {:#?}", text, error);
+            assert_eq!(error.offset().char, at, "{:#?} was reported in the wrong place", text);
+        }
+        let bad = [("so 1/-2", 4), ("1/-2.5", 1), ("0x1f%", 4), ("0x1e-5", 4), ("-0b1/10", 4), ("0o7e5", 3)];
+        for (text,at) in bad {//the same through the lexer, which must read the whole number in
+            let error = parse(text).unwrap_err();
+            assert!(matches!(error, GallifreyanError::InvalidNumber{..}), "{:#?} gave {:#?}", text, error);
+            assert_eq!(error.offset().char, at, "{:#?} was reported in the wrong place", text);
+        }
+    }
+
+    #[test]
+    fn bases() {
+        let number = crate::tree::Number::try_from("0x1F".to_lowercase().chars().collect::<Vec<_>>()).unwrap();
+        assert_eq!(number.base, 16);
+        assert_eq!(number.digits.iter().map(|d| d.value()).collect::<Vec<_>>(), vec![1,15]);
+        assert_eq!(number.digits[1], Digit{num_circles:3, num_lines:0, follows_dot:false, follows_comma:false});
+        assert_eq!(parse("0b1010").unwrap().sentences().next().unwrap().words[0], Number(crate::tree::Number{
+            is_positive:true, is_whole:true, base:2, digits:"1010".chars().map(|c| Digit::try_from(&c,2,false).unwrap()).collect(), suffix:None}));
+        for text in ["0x1f", "-0xff", "0b1010", "0o17", "0x1e5", "0x0.8", "0xa.c"] {
+            assert_eq!(parse(text).unwrap().to_string(), text);
+        }
+        assert_eq!(parse("0b102"), Err(GallifreyanError::InvalidDigit{character:'2', offset:Offset::new(4,4)}));
+        assert_eq!(crate::tree::Number::try_from(vec!['0','x']), Err(GallifreyanError::EmptyNumber{offset:Offset::default()}));
+
+        let number = crate::tree::Number::try_from(vec!['1','2']).unwrap();
+        assert_eq!(number.clone().in_base(8).unwrap().to_string(), "0o12");
+        assert_eq!(number.clone().in_base(16).unwrap().base(), 16);
+        assert!(matches!(number.clone().in_base(5), Err(GallifreyanError::InvalidNumber{..})));//it would be drawn with a ring but written as if it were decimal
+        assert_eq!(number.in_base(2), Err(GallifreyanError::InvalidDigit{character:'2', offset:Offset::default()}));
+    }
+}
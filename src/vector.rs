@@ -0,0 +1,304 @@
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::fmt::Write;
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use crate::shape::{Arc, Cart, Circle, Line, Renderer};
+
+const POINTS_PER_MM:f64 = 72.0/25.4;
+
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub struct PageOptions {//all in points
+    pub width: f64,
+    pub height: f64,
+    pub margin: f64,
+}
+impl PageOptions {
+    pub fn from_mm(width:f64, height:f64, margin:f64) -> Self {
+        Self { width: width*POINTS_PER_MM, height: height*POINTS_PER_MM, margin: margin*POINTS_PER_MM }
+    }
+    pub fn named(name:&str) -> Option<Self> {
+        let (width,height) = match name.to_lowercase().as_str() {
+            "a3" => (297.0, 420.0),
+            "a4" => (210.0, 297.0),
+            "a5" => (148.0, 210.0),
+            "letter" => (215.9, 279.4),
+            "legal" => (215.9, 355.6),
+            _ => return None,
+        };
+        Some(Self::from_mm(width, height, 12.7))
+    }
+}
+impl Default for PageOptions {
+    fn default() -> Self {
+        Self::named("a4").unwrap()
+    }
+}
+
+enum Command {
+    Circle { centre:Cart, radius:f64, width:Option<f64> },//no width means filled
+    Arc { centre:Cart, radius:f64, start:f64, sweep:f64, width:f64 },
+    Line { start:Cart, end:Cart, width:f64 },
+}
+
+#[derive(Default)]
+pub struct Vector {
+    commands: Vec<Command>,
+}
+
+fn point(centre:Cart, radius:f64, angle:f64) -> Cart {
+    Cart::new(centre.x+radius*angle.cos(), centre.y+radius*angle.sin())
+}
+
+fn arc_to_beziers(out:&mut String, centre:Cart, radius:f64, start:f64, sweep:f64) {//pdf has no arcs, so use quarter turns or less of cubic beziers
+    let pieces = (sweep.abs()/FRAC_PI_2).ceil().max(1.0);
+    let step = sweep/pieces;
+    let k = 4.0/3.0*(step/4.0).tan()*radius;
+    let mut angle = start;
+    let begin = point(centre, radius, angle);
+    let _ = writeln!(out, "{:.3} {:.3} m", begin.x, begin.y);
+    for _ in 0..pieces as u32 {
+        let next = angle+step;
+        let from = point(centre, radius, angle);
+        let to = point(centre, radius, next);
+        let c1 = Cart::new(from.x-k*angle.sin(), from.y+k*angle.cos());
+        let c2 = Cart::new(to.x+k*next.sin(), to.y-k*next.cos());
+        let _ = writeln!(out, "{:.3} {:.3} {:.3} {:.3} {:.3} {:.3} c", c1.x, c1.y, c2.x, c2.y, to.x, to.y);
+        angle = next;
+    }
+}
+
+fn fit(size:Cart, page:&PageOptions) -> (f64,Cart) {//scale and offset that centre the drawing inside the margins
+    let room = Cart::new(page.width-2.0*page.margin, page.height-2.0*page.margin);
+    let scale = (room.x/size.x).min(room.y/size.y);
+    let offset = Cart::new((page.width-size.x*scale)/2.0, (page.height-size.y*scale)/2.0);
+    return (scale, offset);
+}
+
+impl Vector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    fn fill_circle(&mut self, centre:Cart, radius:f64) {
+        self.commands.push(Command::Circle{centre, radius, width:None});
+    }
+    fn stroke_circle(&mut self, centre:Cart, radius:f64, width:f64) {
+        self.commands.push(Command::Circle{centre, radius, width:Some(width)});
+    }
+    fn stroke_arc(&mut self, centre:Cart, radius:f64, start:f64, sweep:f64, width:f64) {//angles as the svg sees them, y pointing down
+        self.commands.push(Command::Arc{centre, radius, start, sweep, width});
+    }
+    fn stroke_line(&mut self, start:Cart, end:Cart, width:f64) {
+        self.commands.push(Command::Line{start, end, width});
+    }
+
+    fn pdf_content(&self, size:Cart, page:&PageOptions) -> String {
+        let (scale,offset) = fit(size, page);
+        let mut out = String::new();
+        //flip so y points down like the svg, then everything can use drawing units
+        let _ = writeln!(out, "0 g 0 G 0 J\n{:.5} 0 0 {:.5} {:.3} {:.3} cm", scale, -scale, offset.x, page.height-offset.y);
+        for c in &self.commands {
+            match c {
+                Command::Circle{centre, radius, width} => {
+                    arc_to_beziers(&mut out, *centre, *radius, 0.0, TAU);
+                    match width {
+                        Some(w) => { let _ = writeln!(out, "h {:.4} w S", w); },
+                        None => { let _ = writeln!(out, "h f"); },
+                    }
+                },
+                Command::Arc{centre, radius, start, sweep, width} => {
+                    arc_to_beziers(&mut out, *centre, *radius, *start, *sweep);
+                    let _ = writeln!(out, "{:.4} w S", width);
+                },
+                Command::Line{start, end, width} => {
+                    let _ = writeln!(out, "{:.3} {:.3} m {:.3} {:.3} l {:.4} w S", start.x, start.y, end.x, end.y, width);
+                },
+            }
+        }
+        return out;
+    }
+
+    pub fn to_pdf(&self, size:Cart, page:&PageOptions) -> Vec<u8> {
+        let content = compress_to_vec_zlib(self.pdf_content(size, page).as_bytes(), 6);
+        let objects:Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+            format!("<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.3} {:.3}] /Contents 4 0 R /Resources << >> >>", page.width, page.height).into_bytes(),
+            [format!("<< /Length {} /Filter /FlateDecode >>\nstream\n", content.len()).into_bytes(), content, b"\nendstream".to_vec()].concat(),
+        ];
+        let mut pdf:Vec<u8> = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets:Vec<usize> = Vec::new();
+        for (i,o) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend(format!("{} 0 obj\n", i+1).into_bytes());
+            pdf.extend_from_slice(o);
+            pdf.extend(b"\nendobj\n");
+        }
+        let xref = pdf.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len()+1);
+        for o in offsets {
+            let _ = writeln!(table, "{:010} 00000 n ", o);
+        }
+        let _ = write!(table, "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len()+1, xref);
+        pdf.extend(table.into_bytes());
+        return pdf;
+    }
+
+    pub fn to_eps(&self, size:Cart, page:&PageOptions) -> String {
+        let (scale,offset) = fit(size, page);
+        let mut out = String::new();
+        let _ = writeln!(out, "%!PS-Adobe-3.0 EPSF-3.0\n%%BoundingBox: 0 0 {} {}\n%%HiResBoundingBox: 0 0 {:.3} {:.3}\n%%Creator: gallifreyan\n%%EndComments",
+                         page.width.ceil(), page.height.ceil(), page.width, page.height);
+        let _ = writeln!(out, "gsave\n0 setgray 0 setlinecap\n{:.3} {:.3} translate {:.5} {:.5} scale", offset.x, page.height-offset.y, scale, -scale);
+        for c in &self.commands {
+            match c {
+                Command::Circle{centre, radius, width} => {
+                    let _ = write!(out, "newpath {:.3} {:.3} {:.3} 0 360 arc closepath ", centre.x, centre.y, radius);
+                    match width {
+                        Some(w) => { let _ = writeln!(out, "{:.4} setlinewidth stroke", w); },
+                        None => { let _ = writeln!(out, "fill"); },
+                    }
+                },
+                Command::Arc{centre, radius, start, sweep, width} => {
+                    //the flipped y means arc turns the same way as the svg's positive sweep
+                    let op = if *sweep >= 0.0 {"arc"} else {"arcn"};
+                    let _ = writeln!(out, "newpath {:.3} {:.3} {:.3} {:.4} {:.4} {} {:.4} setlinewidth stroke",
+                                     centre.x, centre.y, radius, start.to_degrees(), (start+sweep).to_degrees(), op, width);
+                },
+                Command::Line{start, end, width} => {
+                    let _ = writeln!(out, "newpath {:.3} {:.3} moveto {:.3} {:.3} lineto {:.4} setlinewidth stroke", start.x, start.y, end.x, end.y, width);
+                },
+            }
+        }
+        let _ = writeln!(out, "grestore\nshowpage\n%%EOF");
+        return out;
+    }
+}
+
+impl Renderer for Vector {
+    fn circle(&mut self, circle:&Circle) {
+        match circle.thickness {
+            Some(t) => self.stroke_circle(circle.centre, circle.radius, t),
+            None => self.fill_circle(circle.centre, circle.radius),
+        }
+    }
+    fn arc(&mut self, arc:&Arc) {
+        let (centre,radius,start,sweep) = arc.centre_and_angles();
+        self.stroke_arc(centre, radius, start, sweep, arc.thickness);
+    }
+    fn line(&mut self, line:&Line) {
+        self.stroke_line(line.start, line.end, line.thickness);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miniz_oxide::inflate::decompress_to_vec_zlib;
+
+    fn find(bytes:&[u8], needle:&str) -> usize {
+        bytes.windows(needle.len()).position(|w| w == needle.as_bytes()).unwrap()
+    }
+
+    fn numbers(text:&str) -> Vec<f64> {
+        text.split_whitespace().filter_map(|n| n.parse().ok()).collect()
+    }
+
+    fn close(a:Cart, b:Cart) -> bool {
+        a.distance(&b) < 0.01
+    }
+
+    fn sample() -> Vector {
+        let mut vector = Vector::new();
+        vector.circle(&Circle::new(Cart::new(10.0, 10.0), 5.0, None));
+        vector.line(&Line::new(Cart::new(0.0, 0.0), Cart::new(40.0, 20.0), 1.0, false));
+        return vector;
+    }
+
+    fn content(pdf:&[u8]) -> String {
+        let start = find(pdf, "stream\n")+"stream\n".len();
+        let end = find(pdf, "\nendstream");
+        let dict = String::from_utf8_lossy(&pdf[find(pdf, "4 0 obj")..start]).to_string();
+        assert!(dict.contains("/Filter /FlateDecode"));
+        assert_eq!(numbers(dict.split("/Length").nth(1).unwrap())[0] as usize, end-start);
+        return String::from_utf8(decompress_to_vec_zlib(&pdf[start..end]).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn pdf_xref() {
+        let pdf = sample().to_pdf(Cart::new(40.0, 20.0), &PageOptions::default());
+        let trailer = String::from_utf8_lossy(&pdf[find(&pdf, "startxref\n")..]).to_string();
+        let xref = numbers(&trailer)[0] as usize;
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        let table = String::from_utf8_lossy(&pdf[xref..]).to_string();
+        let mut lines = table.lines();
+        assert_eq!(lines.next(), Some("xref"));
+        assert_eq!(numbers(lines.next().unwrap()), vec![0.0, 5.0]);
+        assert_eq!(lines.next(), Some("0000000000 65535 f "));
+        for i in 1..5 {
+            let entry = lines.next().unwrap();
+            assert_eq!(entry.len()+1, 20);//every entry is exactly 20 bytes
+            let offset:usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{i} 0 obj\n").as_bytes()), "entry {i} points at {offset}");
+        }
+        assert!(lines.next().unwrap().starts_with("trailer"));
+        content(&pdf);
+    }
+
+    #[test]
+    fn page_box() {
+        let page = PageOptions::from_mm(100.0, 50.0, 10.0);
+        let margin = 10.0*POINTS_PER_MM;
+        let pdf = sample().to_pdf(Cart::new(40.0, 20.0), &page);
+        let object = String::from_utf8_lossy(&pdf[find(&pdf, "3 0 obj")..find(&pdf, "4 0 obj")]).to_string();
+        let media = object.split("/MediaBox [").nth(1).unwrap().split(']').next().unwrap();
+        assert_eq!(numbers(media), vec![0.0, 0.0, 283.465, 141.732]);
+
+        //the drawing is wider than the room left, so it touches the top and bottom margins and is centred across
+        let content = content(&pdf);
+        let cm = numbers(content.lines().find(|l| l.ends_with(" cm")).unwrap());
+        let corner = |x:f64, y:f64| Cart::new(cm[0]*x+cm[4], cm[3]*y+cm[5]);
+        let (top_left,bottom_right) = (corner(0.0, 0.0), corner(40.0, 20.0));
+        assert!((top_left.y-(page.height-margin)).abs() < 0.01);
+        assert!((bottom_right.y-margin).abs() < 0.01);
+        assert!(((top_left.x+bottom_right.x)/2.0-page.width/2.0).abs() < 0.01);
+        assert!(top_left.x > margin);
+
+        let eps = sample().to_eps(Cart::new(40.0, 20.0), &page);
+        assert!(eps.contains("%%BoundingBox: 0 0 284 142\n"));
+        let translate = numbers(eps.lines().find(|l| l.contains(" translate ")).unwrap());
+        assert_eq!((translate[0], translate[1]), ((top_left.x*1000.0).round()/1000.0, (top_left.y*1000.0).round()/1000.0));
+    }
+
+    #[test]
+    fn arcs() {
+        //the same two ends, the short way round in both directions, with where the middle of each should be
+        let start = Cart::new(10.0, 0.0);
+        let end = Cart::new(0.0, 10.0);
+        let tests = [(true, Cart::new(7.071, 7.071)), (false, Cart::new(2.929, 2.929))];
+        for (clockwise,middle) in tests {
+            let mut vector = Vector::new();
+            vector.arc(&Arc::new(start, end, 10.0, false, clockwise, 1.0));
+
+            let pdf = vector.pdf_content(Cart::new(10.0, 10.0), &PageOptions::default());
+            let pieces:Vec<Vec<f64>> = pdf.lines().filter(|l| l.ends_with(" m") || l.ends_with(" c")).map(numbers).collect();
+            assert_eq!(pieces.len(), 2);
+            let (from,curve) = (&pieces[0], &pieces[1]);
+            assert!(close(Cart::new(from[0], from[1]), start));
+            assert!(close(Cart::new(curve[4], curve[5]), end));
+            let half = |i:usize| (from[i]+3.0*curve[i]+3.0*curve[i+2]+curve[i+4])/8.0;//the bezier half way along
+            assert!(close(Cart::new(half(0), half(1)), middle), "{clockwise} went through {}, {}", half(0), half(1));
+
+            let eps = vector.to_eps(Cart::new(10.0, 10.0), &PageOptions::default());
+            let line = eps.lines().find(|l| l.starts_with("newpath")).unwrap();
+            let n = numbers(line);
+            let (centre,radius,first,last) = (Cart::new(n[0], n[1]), n[2], n[3].to_radians(), n[4].to_radians());
+            assert!(close(point(centre, radius, first), start));
+            assert!(close(point(centre, radius, last), end));
+            assert!(close(point(centre, radius, (first+last)/2.0), middle));
+            if clockwise {
+                assert!(line.contains(" arc ") && last > first);
+            } else {
+                assert!(line.contains(" arcn ") && last < first);
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::error::{GallifreyanError, Offset};
+
+//just enough xml to read back the svgs this crate writes, no namespaces or dtds
+
+#[derive(Debug,Clone)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String,String)>,
+    pub children: Vec<Element>,
+    pub text: String,//the text directly inside, between the children
+    pub offset: Offset,//where the start tag begins
+}
+
+impl Element {
+    pub fn attribute(&self, name:&str) -> Option<&str> {
+        self.attributes.iter().find(|(n,_)| n == name).map(|(_,v)| v.as_str())
+    }
+    pub fn has_class(&self, class:&str) -> bool {
+        self.attribute("class").is_some_and(|c| c.split_whitespace().any(|c| c == class))
+    }
+    pub fn elements(&self) -> impl Iterator<Item=&Element> {
+        self.children.iter()
+    }
+    pub fn find(&self, name:&str) -> Option<&Element> {//the first element with this name, searching depth first
+        self.elements().find_map(|e| if e.name == name {Some(e)} else {e.find(name)})
+    }
+    pub fn error(&self, reason:&str) -> GallifreyanError {
+        GallifreyanError::InvalidSvg{reason:reason.to_string(), offset:self.offset}
+    }
+}
+
+fn unescape(text:&str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        result.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let end = rest.find(';').unwrap_or(0);
+        let entity = &rest[1..end.max(1)];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity.strip_prefix("#x").and_then(|h| u32::from_str_radix(h, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match c {
+            Some(c) if end > 0 => {
+                result.push(c);
+                rest = &rest[end+1..];
+            },
+            _ => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    return result;
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,//in bytes
+}
+
+impl<'a> Parser<'a> {
+    fn offset(&self) -> Offset {
+        Offset::new(self.pos, self.text[..self.pos].chars().count())
+    }
+    fn error(&self, reason:&str) -> GallifreyanError {
+        GallifreyanError::InvalidSvg{reason:reason.to_string(), offset:self.offset()}
+    }
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+    fn skip_space(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len()-rest.trim_start().len();
+    }
+    fn skip_past(&mut self, end:&str) -> Result<(),GallifreyanError> {
+        match self.rest().find(end) {
+            Some(i) => {
+                self.pos += i+end.len();
+                Ok(())
+            },
+            None => Err(self.error(&format!("missing {end}"))),
+        }
+    }
+    fn skip_misc(&mut self) -> Result<bool,GallifreyanError> {//comments, processing instructions and doctypes, true if one was skipped
+        let rest = self.rest();
+        if rest.starts_with("<!--") {
+            self.skip_past("-->")?;
+        } else if rest.starts_with("<?") {
+            self.skip_past("?>")?;
+        } else if rest.starts_with("<!") && !rest.starts_with("<![CDATA[") {
+            self.skip_past(">")?;
+        } else {
+            return Ok(false);
+        }
+        return Ok(true);
+    }
+    fn name(&mut self) -> Result<String,GallifreyanError> {
+        let rest = self.rest();
+        let end = rest.find(|c:char| c.is_whitespace() || "/>=".contains(c)).unwrap_or(rest.len());
+        if end == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.pos += end;
+        return Ok(rest[..end].to_string());
+    }
+    fn expect(&mut self, what:&str) -> Result<(),GallifreyanError> {
+        if !self.rest().starts_with(what) {
+            return Err(self.error(&format!("expected {what}")));
+        }
+        self.pos += what.len();
+        return Ok(());
+    }
+    fn attribute(&mut self) -> Result<(String,String),GallifreyanError> {
+        let name = self.name()?;
+        self.skip_space();
+        self.expect("=")?;
+        self.skip_space();
+        let quote = match self.rest().chars().next() {
+            Some(q) if q == '"' || q == '\'' => q,
+            _ => return Err(self.error("expected a quoted value")),
+        };
+        self.pos += 1;
+        let end = self.rest().find(quote).ok_or(self.error("unclosed attribute value"))?;
+        let value = unescape(&self.rest()[..end]);
+        self.pos += end+1;
+        return Ok((name,value));
+    }
+    fn element(&mut self) -> Result<Element,GallifreyanError> {
+        let offset = self.offset();
+        self.expect("<")?;
+        let name = self.name()?;
+        let mut element = Element{name, attributes:Vec::new(), children:Vec::new(), text:String::new(), offset};
+        loop {
+            self.skip_space();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            } else if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let attribute = self.attribute()?;
+            element.attributes.push(attribute);
+        }
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(GallifreyanError::InvalidSvg{reason:format!("<{}> is never closed", element.name), offset});
+            } else if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(self.error(&format!("</{name}> does not close <{}>", element.name)));
+                }
+                self.skip_space();
+                self.expect(">")?;
+                return Ok(element);
+            } else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let end = cdata.find("]]>").ok_or(self.error("unclosed CDATA"))?;
+                element.text.push_str(&cdata[..end]);
+                self.pos += "<![CDATA[".len()+end+"]]>".len();
+            } else if self.skip_misc()? {
+                continue;
+            } else if rest.starts_with('<') {
+                let child = self.element()?;
+                element.children.push(child);
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                element.text.push_str(&unescape(&rest[..end]));
+                self.pos += end;
+            }
+        }
+    }
+}
+
+pub fn parse(text:&str) -> Result<Element,GallifreyanError> {
+    let mut parser = Parser{text, pos:0};
+    loop {
+        parser.skip_space();
+        if !parser.skip_misc()? {
+            break;
+        }
+    }
+    if !parser.rest().starts_with('<') {
+        return Err(parser.error("expected an element"));
+    }
+    let root = parser.element()?;
+    return Ok(root);
+}